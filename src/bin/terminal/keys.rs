@@ -0,0 +1,315 @@
+use crossterm::event::{KeyCode, KeyEventKind};
+use std::fs;
+use std::io;
+use std::time::{Duration, Instant};
+use tetris::interface::game::Input;
+use tetris::interface::input::{Button, InputHandler};
+
+// Lido do diretório atual quando `--keys` não é passado
+pub const KEYS_FILE: &str = "tetris.keys";
+
+// Sem eventos de soltura, a tecla conta como segurada enquanto a repetição
+// do terminal continuar chegando dentro deste intervalo
+const HOLD_TIMEOUT: Duration = Duration::from_millis(120);
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Action {
+    Left,
+    Right,
+    SoftDrop,
+    HardDrop,
+    RotateCw,
+    RotateCcw,
+    Rotate180,
+    Hold,
+    Reset,
+    Ghost,
+    Save,
+    Quit,
+}
+
+impl Action {
+    pub const ALL: [Action; 12] = [
+        Action::Left,
+        Action::Right,
+        Action::SoftDrop,
+        Action::HardDrop,
+        Action::RotateCw,
+        Action::RotateCcw,
+        Action::Rotate180,
+        Action::Hold,
+        Action::Reset,
+        Action::Ghost,
+        Action::Save,
+        Action::Quit,
+    ];
+
+    // Nome usado no arquivo de teclas
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Left => "left",
+            Action::Right => "right",
+            Action::SoftDrop => "soft-drop",
+            Action::HardDrop => "hard-drop",
+            Action::RotateCw => "rotate",
+            Action::RotateCcw => "rotate-ccw",
+            Action::Rotate180 => "rotate-180",
+            Action::Hold => "hold",
+            Action::Reset => "restart",
+            Action::Ghost => "ghost",
+            Action::Save => "save",
+            Action::Quit => "quit",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Action::Left => "Move Left",
+            Action::Right => "Move Right",
+            Action::SoftDrop => "Soft Drop",
+            Action::HardDrop => "Hard Drop",
+            Action::RotateCw => "Rotate",
+            Action::RotateCcw => "Rotate CCW",
+            Action::Rotate180 => "Rotate 180",
+            Action::Hold => "Hold",
+            Action::Reset => "Restart",
+            Action::Ghost => "Toggle Ghost",
+            Action::Save => "Save & Quit",
+            Action::Quit => "Quit",
+        }
+    }
+
+    // Ações que ficam com o `InputHandler` enquanto a tecla está apertada
+    pub fn button(self) -> Option<Button> {
+        match self {
+            Action::Left => Some(Button::Left),
+            Action::Right => Some(Button::Right),
+            Action::SoftDrop => Some(Button::SoftDrop),
+            _ => None,
+        }
+    }
+
+    pub fn input(self) -> Option<Input> {
+        match self {
+            Action::HardDrop => Some(Input::HardDrop),
+            Action::RotateCw => Some(Input::RotateCw),
+            Action::RotateCcw => Some(Input::RotateCcw),
+            Action::Rotate180 => Some(Input::Rotate180),
+            Action::Hold => Some(Input::Hold),
+            Action::Reset => Some(Input::Reset),
+            _ => None,
+        }
+    }
+
+    fn default_keys(self) -> Vec<KeyCode> {
+        match self {
+            Action::Left => vec![KeyCode::Left, KeyCode::Char('a')],
+            Action::Right => vec![KeyCode::Right, KeyCode::Char('d')],
+            Action::SoftDrop => vec![KeyCode::Down, KeyCode::Char('s')],
+            Action::HardDrop => vec![KeyCode::Char(' '), KeyCode::Char('x')],
+            Action::RotateCw => vec![KeyCode::Up, KeyCode::Char('w')],
+            Action::RotateCcw => vec![KeyCode::Char('z')],
+            Action::Rotate180 => vec![KeyCode::Char('e')],
+            Action::Hold => vec![KeyCode::Char('c')],
+            Action::Reset => vec![KeyCode::Char('r')],
+            Action::Ghost => vec![KeyCode::Char('g')],
+            Action::Save => vec![KeyCode::Char('p')],
+            Action::Quit => vec![KeyCode::Char('q'), KeyCode::Esc],
+        }
+    }
+}
+
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let key = match name.to_lowercase().as_str() {
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "space" => KeyCode::Char(' '),
+        "enter" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "esc" => KeyCode::Esc,
+        "backspace" => KeyCode::Backspace,
+        name => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                _ => return None,
+            }
+        }
+    };
+    Some(key)
+}
+
+fn key_name(key: KeyCode) -> String {
+    match key {
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        key => format!("{:?}", key),
+    }
+}
+
+/// Teclas de cada ação. O arquivo de teclas tem uma ação por linha seguida
+/// das teclas (`left a left`); ações ausentes mantêm as teclas padrão.
+#[derive(Debug, Clone)]
+pub struct Bindings {
+    keys: Vec<(Action, Vec<KeyCode>)>,
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self {
+            keys: Action::ALL
+                .iter()
+                .map(|&action| (action, action.default_keys()))
+                .collect(),
+        }
+    }
+}
+
+impl Bindings {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut bindings = Self::default();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let mut words = line.split_whitespace();
+            let Some(name) = words.next() else {
+                continue;
+            };
+            let error = |message: &str| format!("line {}: {}", number + 1, message);
+
+            let (_, keys) = bindings
+                .keys
+                .iter_mut()
+                .find(|(action, _)| action.name() == name)
+                .ok_or_else(|| error(&format!("unknown action {:?}", name)))?;
+            *keys = words
+                .map(|word| parse_key(word).ok_or_else(|| error(&format!("unknown key {:?}", word))))
+                .collect::<Result<_, _>>()?;
+            if keys.is_empty() {
+                return Err(error(&format!("no keys for {:?}", name)));
+            }
+        }
+        Ok(bindings)
+    }
+
+    // Um arquivo que não existe deixa as teclas padrão
+    pub fn load(path: &str) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|e| format!("{}: {}", path, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {}", path, e)),
+        }
+    }
+
+    // Letras valem com ou sem Shift
+    pub fn action(&self, key: KeyCode) -> Option<Action> {
+        let key = match key {
+            KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+            key => key,
+        };
+        self.keys
+            .iter()
+            .find(|(_, keys)| keys.contains(&key))
+            .map(|&(action, _)| action)
+    }
+
+    // Linhas de ajuda para o painel de controles
+    pub fn controls(&self) -> Vec<String> {
+        self.keys
+            .iter()
+            .map(|(action, keys)| {
+                let keys: Vec<String> = keys.iter().map(|&key| key_name(key)).collect();
+                format!("{:<9} - {}", keys.join("/"), action.label())
+            })
+            .collect()
+    }
+}
+
+/// Repassa teclas seguradas ao `InputHandler`. Terminais que não informam
+/// quando a tecla é solta dependem da repetição automática dela.
+#[derive(Debug)]
+pub struct HeldKeys {
+    reports_releases: bool,
+    deadlines: Vec<(Button, Instant)>,
+}
+
+impl HeldKeys {
+    pub fn new(reports_releases: bool) -> Self {
+        Self {
+            reports_releases,
+            deadlines: Vec::new(),
+        }
+    }
+
+    pub fn key_event(&mut self, handler: &mut InputHandler, button: Button, kind: KeyEventKind, now: Instant) {
+        if kind == KeyEventKind::Release {
+            handler.release(button);
+            return;
+        }
+
+        handler.press(button);
+        if !self.reports_releases {
+            self.deadlines.retain(|&(held, _)| held != button);
+            self.deadlines.push((button, now + HOLD_TIMEOUT));
+        }
+    }
+
+    // Solta as teclas cuja repetição parou
+    pub fn expire(&mut self, handler: &mut InputHandler, now: Instant) {
+        self.deadlines.retain(|&(button, deadline)| {
+            if deadline <= now {
+                handler.release(button);
+            }
+            deadline > now
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, Bindings, HeldKeys, HOLD_TIMEOUT};
+    use crossterm::event::{KeyCode, KeyEventKind};
+    use std::time::Instant;
+    use tetris::interface::input::{Button, InputHandler};
+
+    #[test]
+    fn key_files_replace_the_default_keys_of_an_action() {
+        let bindings = Bindings::parse("# setas para girar\nrotate up x\nhard-drop space\n").unwrap();
+        assert_eq!(bindings.action(KeyCode::Char('X')), Some(Action::RotateCw));
+        assert_eq!(bindings.action(KeyCode::Char('w')), None);
+        assert_eq!(bindings.action(KeyCode::Left), Some(Action::Left));
+
+        assert!(Bindings::parse("jump space").is_err());
+        assert!(Bindings::parse("hold ctrl").is_err());
+        assert!(Bindings::parse("hold").is_err());
+    }
+
+    #[test]
+    fn held_keys_expire_when_the_repeat_stops() {
+        let mut handler = InputHandler::default();
+        let mut keys = HeldKeys::new(false);
+        let start = Instant::now();
+        keys.key_event(&mut handler, Button::Left, KeyEventKind::Press, start);
+        keys.key_event(&mut handler, Button::Left, KeyEventKind::Press, start + HOLD_TIMEOUT / 2);
+        keys.expire(&mut handler, start + HOLD_TIMEOUT);
+        assert!(handler.is_held(Button::Left));
+        keys.expire(&mut handler, start + HOLD_TIMEOUT * 2);
+        assert!(!handler.is_held(Button::Left));
+
+        let mut keys = HeldKeys::new(true);
+        keys.key_event(&mut handler, Button::Right, KeyEventKind::Press, start);
+        keys.expire(&mut handler, start + HOLD_TIMEOUT * 10);
+        assert!(handler.is_held(Button::Right));
+        keys.key_event(&mut handler, Button::Right, KeyEventKind::Release, start);
+        assert!(!handler.is_held(Button::Right));
+    }
+}
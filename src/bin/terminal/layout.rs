@@ -0,0 +1,98 @@
+use crate::render::{Canvas, Style};
+use crate::style::ColorMode;
+use tetris::engine::piece::Cell;
+use tetris::engine::position::Pos;
+use tetris::engine::shape::Shape;
+use tetris::interface::ui::{wrap, Frame, Tile, Tone};
+
+// Largura do painel à esquerda do campo (hold, pontuação e avisos)
+const LEFT_PANEL: u16 = 18;
+// Espaço entre o campo e os painéis da direita
+const PANEL_GAP: u16 = 3;
+
+fn draw_mini(canvas: &mut Canvas, mode: ColorMode, x: u16, y: u16, shape: Option<&Shape>) {
+    let width = mode.cell_width();
+    for row in 0..2 {
+        for col in 0..4 {
+            let (text, style) = match shape {
+                Some(shape) if shape.has_position(Pos(col, row)) => mode.cell(Cell::Piece(shape.kind())),
+                _ => (mode.blank(), Style::default()),
+            };
+            canvas.styled(x + col as u16 * width, y + row as u16, text, style);
+        }
+    }
+}
+
+// Campo no meio, hold, pontuação e avisos à esquerda, próximas peças e
+// controles à direita
+pub fn draw_frame(canvas: &mut Canvas, mode: ColorMode, frame: &Frame) {
+    canvas.text(0, 0, "TETRIS");
+    canvas.text(0, 2, "HOLD");
+    draw_mini(canvas, mode, 0, 3, frame.hold.as_ref());
+
+    let stats = [
+        ("SCORE", frame.score.to_string()),
+        ("LEVEL", frame.level.to_string()),
+        ("LINES", frame.lines.to_string()),
+        ("TIME", frame.time()),
+    ];
+    let mut y = 6;
+    for (label, value) in stats {
+        canvas.text(0, y, &format!("{:<6}{}", label, value));
+        y += 1;
+    }
+
+    // Estado, última limpeza e avisos, separados por uma linha em branco
+    let (status, tone) = frame.status();
+    let mut notes = vec![(status, tone)];
+    if let Some(clear) = frame.last_clear {
+        notes.push((clear.to_string(), Tone::Highlight));
+    }
+    notes.extend(frame.banners.iter().map(|banner| (banner.text.clone(), banner.tone)));
+    for (text, tone) in notes {
+        y += 1;
+        for line in wrap(&text, LEFT_PANEL as usize - 2) {
+            canvas.styled(0, y, &line, mode.tone(tone));
+            y += 1;
+        }
+    }
+
+    // Draw grid
+    let cell_width = mode.cell_width();
+    let well_width = frame.width as u16 * cell_width;
+    let border = mode.border();
+    let [top_left, top_right, bottom_left, bottom_right] = border.corners;
+    let horizontal = border.horizontal.to_string().repeat(well_width as usize);
+    canvas.text(LEFT_PANEL, 0, &format!("{}{}{}", top_left, horizontal, top_right));
+
+    for y in 0..frame.height {
+        let row = y as u16 + 1;
+        canvas.text(LEFT_PANEL, row, &border.vertical.to_string());
+        let flash = frame.flash_rows.contains(&y);
+        for x in 0..frame.width {
+            let (text, style) = match frame.tile(Pos(x, y)) {
+                _ if flash => mode.flash(),
+                Tile::Cell(cell) => mode.cell(cell),
+                Tile::Ghost(kind) => mode.ghost(kind),
+            };
+            canvas.styled(LEFT_PANEL + 1 + x as u16 * cell_width, row, text, style);
+        }
+        canvas.text(LEFT_PANEL + 1 + well_width, row, &border.vertical.to_string());
+    }
+
+    let bottom = frame.height as u16 + 1;
+    canvas.text(LEFT_PANEL, bottom, &format!("{}{}{}", bottom_left, horizontal, bottom_right));
+
+    // Próximas peças, uma embaixo da outra
+    let next_x = LEFT_PANEL + well_width + 2 + PANEL_GAP;
+    canvas.text(next_x, 0, "NEXT");
+    for (i, shape) in frame.next.iter().enumerate() {
+        draw_mini(canvas, mode, next_x, 2 + 3 * i as u16, Some(shape));
+    }
+
+    let controls_x = next_x + 4 * cell_width + PANEL_GAP;
+    canvas.text(controls_x, 0, "Controls");
+    for (i, line) in frame.controls.iter().enumerate() {
+        canvas.text(controls_x, 2 + i as u16, line);
+    }
+}
@@ -0,0 +1,288 @@
+mod keys;
+mod layout;
+mod render;
+mod style;
+mod term;
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use keys::{Action, Bindings, HeldKeys, KEYS_FILE};
+use std::fs;
+use std::io;
+use std::time::{Duration, Instant};
+use style::ColorMode;
+use term::RawTerminal;
+use tetris::interface::game::{Game, Input, FRAME};
+use tetris::interface::input::InputHandler;
+use tetris::interface::replay::{Player, Recorder, Replay, REPLAY_FILE, SEEK_FRAMES};
+use tetris::interface::snapshot::SAVE_FILE;
+use tetris::interface::ui::{Mode, Renderer, View};
+
+const REPLAY_CONTROLS: [&str; 5] = [
+    "Space/p - Pause/Resume",
+    "+/-     - Faster/Slower",
+    "←/→     - Seek 5s Back/Forward",
+    "g       - Toggle Ghost",
+    "q/Esc   - Quit",
+];
+
+const CONTINUE_CONTROLS: [&str; 2] = ["y       - Continue", "n/Esc   - New Game"];
+
+// Em modo raw o Ctrl+C chega como tecla, não como sinal
+fn is_interrupt(key: &KeyEvent) -> bool {
+    key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c')
+}
+
+// Mostra a partida salva e pergunta se deve continuar; `None` no Ctrl+C
+fn ask_continue(terminal: &mut RawTerminal, game: &Game) -> io::Result<Option<bool>> {
+    let mut view = View::new(CONTINUE_CONTROLS.iter().map(|line| line.to_string()).collect());
+    let frame = view.frame(game.tetris(), game.frame(), Mode::Continue);
+    terminal.render(&frame)?;
+    loop {
+        let key = match event::read()? {
+            Event::Key(key) => key,
+            Event::Resize(..) => {
+                terminal.invalidate();
+                terminal.render(&frame)?;
+                continue;
+            }
+            _ => continue,
+        };
+        if is_interrupt(&key) {
+            return Ok(None);
+        }
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => return Ok(Some(true)),
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => return Ok(Some(false)),
+            _ => {}
+        }
+    }
+}
+
+// Continua a partida salva ou começa uma nova, gravada para replay
+fn start(
+    terminal: &mut RawTerminal,
+    bindings: &Bindings,
+    saved: Option<Game>,
+) -> io::Result<String> {
+    let saved = match saved {
+        Some(game) => match ask_continue(terminal, &game)? {
+            Some(resume) => resume.then_some(game),
+            None => return Ok("Thanks for playing!".to_string()),
+        },
+        None => None,
+    };
+    match saved {
+        Some(game) => play(terminal, bindings, game, None),
+        None => {
+            let (game, recorder) = Recorder::new_game(10, 20);
+            play(terminal, bindings, game, Some(recorder))
+        }
+    }
+}
+
+// Devolve a mensagem a mostrar depois que o terminal volta ao normal
+fn play(
+    terminal: &mut RawTerminal,
+    bindings: &Bindings,
+    mut game: Game,
+    mut recorder: Option<Recorder>,
+) -> io::Result<String> {
+    let mut view = View::new(bindings.controls());
+    let mut handler = InputHandler::default();
+    let mut held = HeldKeys::new(terminal.reports_releases());
+    let mut inputs: Vec<Input> = Vec::new();
+    let mut last_frame = Instant::now();
+
+    let farewell = 'running: loop {
+        // A entrada só é esperada até o próximo frame; a gravidade não para
+        let mut timeout = game.until_next_frame();
+        while event::poll(timeout)? {
+            timeout = Duration::ZERO;
+            let key = match event::read()? {
+                Event::Key(key) => key,
+                // Alguns terminais bagunçam a tela ao redimensionar
+                Event::Resize(..) => {
+                    terminal.invalidate();
+                    continue;
+                }
+                _ => continue,
+            };
+            if is_interrupt(&key) {
+                break 'running format!("Thanks for playing! Final score: {}", game.tetris().score());
+            }
+            let Some(action) = bindings.action(key.code) else {
+                continue;
+            };
+            if let Some(button) = action.button() {
+                held.key_event(&mut handler, button, key.kind, Instant::now());
+                continue;
+            }
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match action {
+                Action::Ghost => view.toggle_ghost(),
+                // Uma partida encerrada não tem o que continuar
+                Action::Save if game.tetris().is_game_over() => {
+                    break 'running format!("The game is over, nothing to save. Final score: {}", game.tetris().score());
+                }
+                Action::Save => {
+                    break 'running match fs::write(SAVE_FILE, game.snapshot().to_string()) {
+                        Ok(()) => format!("Game saved to {}", SAVE_FILE),
+                        Err(e) => format!("Could not save the game: {}", e),
+                    };
+                }
+                Action::Quit => {
+                    break 'running format!("Thanks for playing! Final score: {}", game.tetris().score());
+                }
+                _ => inputs.extend(action.input()),
+            }
+        }
+        held.expire(&mut handler, Instant::now());
+
+        let now = Instant::now();
+        // Teclas apertadas desde o último frame entram no próximo
+        let frames = game.advance(now - last_frame, |tetris| {
+            let mut frame_inputs = std::mem::take(&mut inputs);
+            frame_inputs.extend(handler.frame_inputs(tetris));
+            frame_inputs
+        });
+        last_frame = now;
+        if frames.is_empty() {
+            continue;
+        }
+        if let Some(recorder) = &mut recorder {
+            frames.iter().for_each(|inputs| recorder.record_frame(inputs));
+        }
+
+        let frame = game.frame();
+        view.update(game.tetris_mut().drain_events(), frame);
+        terminal.render(&view.frame(game.tetris(), frame, Mode::Playing))?;
+    };
+
+    if let Some(recorder) = recorder {
+        if let Err(e) = fs::write(REPLAY_FILE, recorder.replay().to_bytes()) {
+            return Ok(format!("{}\nCould not write the replay: {}", farewell, e));
+        }
+    }
+    Ok(farewell)
+}
+
+fn watch(terminal: &mut RawTerminal, replay: Replay) -> io::Result<()> {
+    let mut view = View::new(REPLAY_CONTROLS.iter().map(|line| line.to_string()).collect());
+    let mut player = Player::new(replay);
+    let mut last_frame = Instant::now();
+
+    loop {
+        let mut timeout = FRAME;
+        while event::poll(timeout)? {
+            timeout = Duration::ZERO;
+            let key = match event::read()? {
+                Event::Key(key) => key,
+                // Alguns terminais bagunçam a tela ao redimensionar
+                Event::Resize(..) => {
+                    terminal.invalidate();
+                    continue;
+                }
+                _ => continue,
+            };
+            if is_interrupt(&key) {
+                return Ok(());
+            }
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char(' ') | KeyCode::Char('p') => player.toggle_pause(),
+                KeyCode::Char('+') | KeyCode::Char('=') => player.set_speed(player.speed() * 2.0),
+                KeyCode::Char('-') => player.set_speed(player.speed() / 2.0),
+                KeyCode::Right => player.seek(player.frame() + SEEK_FRAMES),
+                KeyCode::Left => player.seek(player.frame().saturating_sub(SEEK_FRAMES)),
+                KeyCode::Char('g') => view.toggle_ghost(),
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                _ => {}
+            }
+        }
+
+        let now = Instant::now();
+        player.update(now - last_frame);
+        last_frame = now;
+        let frame = player.frame();
+        view.update(player.tetris_mut().drain_events(), frame);
+
+        let mode = Mode::Replay {
+            paused: player.is_paused(),
+            speed: player.speed(),
+            total_frames: player.total_frames(),
+        };
+        terminal.render(&view.frame(player.tetris(), frame, mode))?;
+    }
+}
+
+fn main() {
+    // `terminal --replay arquivo` assiste a uma partida gravada,
+    // `terminal --keys arquivo` troca as teclas padrão e
+    // `terminal --color modo` escolhe entre truecolor, 256, 16 e ascii
+    let args: Vec<String> = std::env::args().collect();
+    let option = |name: &str, default: &'static str| {
+        args.iter()
+            .position(|arg| arg == name)
+            .map(|i| args.get(i + 1).map_or(default, String::as_str))
+    };
+
+    let mode = match option("--color", "auto") {
+        None | Some("auto") => ColorMode::detect(),
+        Some(name) => match name.parse() {
+            Ok(mode) => mode,
+            Err(e) => {
+                println!("{}", e);
+                return;
+            }
+        },
+    };
+
+    if let Some(path) = option("--replay", REPLAY_FILE) {
+        let replay = fs::read(path)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| Replay::from_bytes(&bytes).map_err(|e| e.to_string()));
+        let result = match replay {
+            Ok(replay) => RawTerminal::enter(mode).and_then(|mut terminal| watch(&mut terminal, replay)),
+            Err(e) => {
+                println!("Could not load {}: {}", path, e);
+                return;
+            }
+        };
+        if let Err(e) = result {
+            println!("Terminal error: {}", e);
+        }
+        return;
+    }
+
+    let bindings = match Bindings::load(option("--keys", KEYS_FILE).unwrap_or(KEYS_FILE)) {
+        Ok(bindings) => bindings,
+        Err(e) => {
+            println!("Could not load the key bindings: {}", e);
+            return;
+        }
+    };
+
+    println!("Starting Tetris...");
+
+    // O arquivo é apagado depois de lido
+    let saved = Game::load_saved(SAVE_FILE).unwrap_or_else(|e| {
+        println!("Could not load the saved game: {}", e);
+        None
+    });
+
+    // O terminal é restaurado ao descartar `terminal`, antes da despedida
+    let result = RawTerminal::enter(mode).and_then(|mut terminal| start(&mut terminal, &bindings, saved));
+    match result {
+        Ok(farewell) => println!("{}", farewell),
+        Err(e) => println!("Terminal error: {}", e),
+    }
+    println!("👋 Goodbye!");
+}
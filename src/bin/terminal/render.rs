@@ -0,0 +1,209 @@
+use crossterm::cursor::MoveTo;
+use crossterm::queue;
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crossterm::terminal::{Clear, ClearType};
+use std::io::{self, Write};
+
+// Emojis e os quadrados do campo ocupam duas colunas no terminal
+fn is_wide(c: char) -> bool {
+    c >= '\u{1F000}' || matches!(c, '⬛' | '⬜' | '✨')
+}
+
+/// Cores de uma célula da tela; `None` deixa a cor padrão do terminal.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Glyph {
+    Char(char, Style),
+    // Segunda coluna de um caractere largo
+    Continuation,
+}
+
+const BLANK: Glyph = Glyph::Char(' ', Style { fg: None, bg: None });
+
+/// Um quadro da tela, coluna a coluna. Escritas fora da tela são ignoradas.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Canvas {
+    width: u16,
+    height: u16,
+    glyphs: Vec<Glyph>,
+}
+
+impl Canvas {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            glyphs: vec![BLANK; width as usize * height as usize],
+        }
+    }
+
+    fn index(&self, x: u16, y: u16) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+
+    // Escreve numa linha só, a partir de (x, y)
+    pub fn text(&mut self, x: u16, y: u16, text: &str) {
+        self.styled(x, y, text, Style::default());
+    }
+
+    pub fn styled(&mut self, x: u16, y: u16, text: &str, style: Style) {
+        if y >= self.height {
+            return;
+        }
+        let mut x = x;
+        for c in text.chars() {
+            let wide = is_wide(c);
+            let columns = if wide { 2 } else { 1 };
+            if x + columns > self.width {
+                break;
+            }
+            let i = self.index(x, y);
+            self.glyphs[i] = Glyph::Char(c, style);
+            if wide {
+                self.glyphs[i + 1] = Glyph::Continuation;
+            }
+            x += columns;
+        }
+    }
+}
+
+// Só troca a cor que mudou
+fn set_style(out: &mut impl Write, from: Style, to: Style) -> io::Result<()> {
+    if from.fg != to.fg {
+        queue!(out, SetForegroundColor(to.fg.unwrap_or(Color::Reset)))?;
+    }
+    if from.bg != to.bg {
+        queue!(out, SetBackgroundColor(to.bg.unwrap_or(Color::Reset)))?;
+    }
+    Ok(())
+}
+
+/// Guarda o quadro que está na tela e, a cada novo quadro, escreve só os
+/// trechos de linha que mudaram.
+#[derive(Debug, Default)]
+pub struct Renderer {
+    front: Canvas,
+}
+
+impl Renderer {
+    // Esquece o que está na tela; o próximo quadro é desenhado inteiro
+    pub fn invalidate(&mut self) {
+        self.front = Canvas::default();
+    }
+
+    pub fn draw(&mut self, out: &mut impl Write, frame: Canvas) -> io::Result<()> {
+        if (frame.width, frame.height) != (self.front.width, self.front.height) {
+            queue!(out, Clear(ClearType::All))?;
+            self.front = Canvas::new(frame.width, frame.height);
+        }
+
+        // Entre quadros o terminal fica sempre nas cores padrão
+        let mut current = Style::default();
+        for y in 0..frame.height {
+            let mut x = 0;
+            while x < frame.width {
+                if frame.glyphs[frame.index(x, y)] == self.front.glyphs[frame.index(x, y)] {
+                    x += 1;
+                    continue;
+                }
+
+                queue!(out, MoveTo(x, y))?;
+                let mut run = String::new();
+                while x < frame.width {
+                    let i = frame.index(x, y);
+                    let glyph = frame.glyphs[i];
+                    if glyph == self.front.glyphs[i] && glyph != Glyph::Continuation {
+                        break;
+                    }
+                    if let Glyph::Char(c, style) = glyph {
+                        if style != current {
+                            queue!(out, Print(std::mem::take(&mut run)))?;
+                            set_style(out, current, style)?;
+                            current = style;
+                        }
+                        run.push(c);
+                    }
+                    x += 1;
+                }
+                queue!(out, Print(run))?;
+            }
+        }
+        if current != Style::default() {
+            queue!(out, ResetColor)?;
+        }
+
+        self.front = frame;
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Canvas, Renderer, Style};
+    use crossterm::style::Color;
+
+    fn draw(renderer: &mut Renderer, text: &str) -> String {
+        let mut canvas = Canvas::new(8, 2);
+        canvas.text(0, 1, text);
+        let mut out = Vec::new();
+        renderer.draw(&mut out, canvas).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn only_changed_cells_are_written() {
+        let mut renderer = Renderer::default();
+        assert_eq!(draw(&mut renderer, "ab🟦c"), "\x1b[2J\x1b[2;1Hab🟦c");
+        assert_eq!(draw(&mut renderer, "ab🟦c"), "");
+        assert_eq!(draw(&mut renderer, "ab🟥c"), "\x1b[2;3H🟥");
+        assert_eq!(draw(&mut renderer, "ab🟥d"), "\x1b[2;5Hd");
+
+        renderer.invalidate();
+        assert!(draw(&mut renderer, "ab🟥d").ends_with("ab🟥d"));
+    }
+
+    #[test]
+    fn colours_are_set_only_when_they_change() {
+        let mut renderer = Renderer::default();
+        let red = Style {
+            fg: Some(Color::Red),
+            bg: None,
+        };
+        let mut canvas = Canvas::new(4, 1);
+        canvas.styled(0, 0, "██", red);
+        canvas.text(2, 0, "x");
+        let mut out = Vec::new();
+        renderer.draw(&mut out, canvas.clone()).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[2J\x1b[1;1H\x1b[38;5;9m██\x1b[39mx"
+        );
+
+        // Mesma cor, outro texto: nada de códigos de cor
+        canvas.styled(0, 0, "▓", red);
+        let mut out = Vec::new();
+        renderer.draw(&mut out, canvas).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\x1b[1;1H\x1b[38;5;9m▓\x1b[0m");
+    }
+
+    #[test]
+    fn text_is_clipped_at_the_edge() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.text(0, 0, "a🟦");
+        canvas.text(0, 5, "hidden");
+        let mut expected = Canvas::new(2, 1);
+        expected.text(0, 0, "a");
+        assert_eq!(canvas, expected);
+
+        let mut canvas = Canvas::new(3, 1);
+        canvas.text(1, 0, "ab🟦");
+        let mut expected = Canvas::new(3, 1);
+        expected.text(1, 0, "ab");
+        assert_eq!(canvas, expected);
+    }
+}
@@ -0,0 +1,269 @@
+use crate::render::Style;
+use crossterm::style::Color;
+use std::env;
+use std::str::FromStr;
+use tetris::engine::piece::{Cell, PieceKind};
+use tetris::interface::palette::{cell_color, Rgb};
+use tetris::interface::ui::Tone;
+
+/// Como desenhar as células: blocos coloridos com a paleta da versão SDL2 em
+/// truecolor, aproximada em 256 ou 16 cores, ou letras como `[I]` em ASCII.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ColorMode {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+    Ascii,
+}
+
+/// Caracteres da moldura do campo.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Border {
+    pub horizontal: char,
+    pub vertical: char,
+    // Superior esquerdo, superior direito, inferior esquerdo, inferior direito
+    pub corners: [char; 4],
+}
+
+impl FromStr for ColorMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "truecolor" | "24bit" => Ok(ColorMode::TrueColor),
+            "256" => Ok(ColorMode::Ansi256),
+            "16" => Ok(ColorMode::Ansi16),
+            "ascii" => Ok(ColorMode::Ascii),
+            _ => Err(format!(
+                "unknown color mode {:?} (use truecolor, 256, 16 or ascii)",
+                s
+            )),
+        }
+    }
+}
+
+impl ColorMode {
+    // Pelas variáveis de ambiente que os terminais costumam definir
+    pub fn detect() -> Self {
+        let no_color = env::var("NO_COLOR").is_ok_and(|value| !value.is_empty());
+        let colorterm = env::var("COLORTERM").ok();
+        let term = env::var("TERM").ok();
+        Self::from_env(no_color, colorterm.as_deref(), term.as_deref())
+    }
+
+    fn from_env(no_color: bool, colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if no_color {
+            return ColorMode::Ascii;
+        }
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            return ColorMode::TrueColor;
+        }
+        match term {
+            // O console do Windows não define TERM, mas tem cores
+            None if cfg!(windows) => ColorMode::Ansi16,
+            None | Some("dumb") => ColorMode::Ascii,
+            Some(term) if term.contains("256color") => ColorMode::Ansi256,
+            Some(_) => ColorMode::Ansi16,
+        }
+    }
+
+    // Colunas do terminal por célula do campo
+    pub fn cell_width(self) -> u16 {
+        match self {
+            ColorMode::Ascii => 3,
+            _ => 2,
+        }
+    }
+
+    // Fundo das prévias de hold e next
+    pub fn blank(self) -> &'static str {
+        match self {
+            ColorMode::Ascii => "   ",
+            _ => "  ",
+        }
+    }
+
+    pub fn border(self) -> Border {
+        match self {
+            ColorMode::Ascii => Border {
+                horizontal: '-',
+                vertical: '|',
+                corners: ['+'; 4],
+            },
+            _ => Border {
+                horizontal: '─',
+                vertical: '│',
+                corners: ['┌', '┐', '└', '┘'],
+            },
+        }
+    }
+
+    // Cor RGB da paleta no modo atual; no de 16 cores, `basic` vale
+    fn rgb(self, rgb: Rgb, basic: Option<Color>) -> Option<Color> {
+        let Rgb(r, g, b) = rgb;
+        match self {
+            ColorMode::TrueColor => Some(Color::Rgb { r, g, b }),
+            ColorMode::Ansi256 => Some(Color::AnsiValue(ansi256(rgb))),
+            ColorMode::Ansi16 => basic,
+            ColorMode::Ascii => None,
+        }
+    }
+
+    fn color(self, cell: Cell) -> Option<Color> {
+        self.rgb(cell_color(cell), ansi16(cell))
+    }
+
+    pub fn tone(self, tone: Tone) -> Style {
+        let basic = match tone {
+            Tone::Info => Color::Blue,
+            Tone::Good => Color::Green,
+            Tone::Bad => Color::Red,
+            Tone::Highlight => Color::Yellow,
+        };
+        Style {
+            fg: self.rgb(tone.color(), Some(basic)),
+            bg: None,
+        }
+    }
+
+    // Linhas que acabaram de ser limpas
+    pub fn flash(self) -> (&'static str, Style) {
+        match self {
+            ColorMode::Ascii => ("===", Style::default()),
+            _ => (
+                "██",
+                Style {
+                    fg: self.rgb(Rgb(255, 255, 255), Some(Color::White)),
+                    bg: None,
+                },
+            ),
+        }
+    }
+
+    pub fn cell(self, cell: Cell) -> (&'static str, Style) {
+        match (self, cell) {
+            (ColorMode::Ascii, Cell::Empty) => (" . ", Style::default()),
+            (ColorMode::Ascii, Cell::Garbage) => ("[#]", Style::default()),
+            (ColorMode::Ascii, Cell::Piece(kind)) => (ascii_piece(kind), Style::default()),
+            // Sem um azul escuro confiável, o vazio em 16 cores é pontilhado
+            (ColorMode::Ansi16, Cell::Empty) => (
+                " .",
+                Style {
+                    fg: Some(Color::DarkGrey),
+                    bg: None,
+                },
+            ),
+            (_, Cell::Empty) => (
+                "  ",
+                Style {
+                    fg: None,
+                    bg: self.color(Cell::Empty),
+                },
+            ),
+            (_, cell) => (
+                "██",
+                Style {
+                    fg: self.color(cell),
+                    bg: None,
+                },
+            ),
+        }
+    }
+
+    // Peça fantasma, sobre o fundo do campo
+    pub fn ghost(self, kind: PieceKind) -> (&'static str, Style) {
+        match self {
+            ColorMode::Ascii => ("[ ]", Style::default()),
+            _ => (
+                "░░",
+                Style {
+                    fg: self.color(Cell::Piece(kind)),
+                    bg: self.cell(Cell::Empty).1.bg,
+                },
+            ),
+        }
+    }
+}
+
+fn ascii_piece(kind: PieceKind) -> &'static str {
+    match kind {
+        PieceKind::I => "[I]",
+        PieceKind::O => "[O]",
+        PieceKind::T => "[T]",
+        PieceKind::S => "[S]",
+        PieceKind::Z => "[Z]",
+        PieceKind::J => "[J]",
+        PieceKind::L => "[L]",
+    }
+}
+
+// Escolhidas à mão: pela distância, S e I cairiam ambas no ciano
+fn ansi16(cell: Cell) -> Option<Color> {
+    match cell {
+        Cell::Piece(PieceKind::I) => Some(Color::Cyan),
+        Cell::Piece(PieceKind::O) => Some(Color::Yellow),
+        Cell::Piece(PieceKind::T) => Some(Color::Magenta),
+        Cell::Piece(PieceKind::S) => Some(Color::Green),
+        Cell::Piece(PieceKind::Z) => Some(Color::Red),
+        Cell::Piece(PieceKind::J) => Some(Color::Blue),
+        Cell::Piece(PieceKind::L) => Some(Color::DarkYellow),
+        Cell::Garbage => Some(Color::Grey),
+        Cell::Empty => None,
+    }
+}
+
+fn distance(Rgb(r1, g1, b1): Rgb, Rgb(r2, g2, b2): Rgb) -> i32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+// Cor mais próxima entre o cubo 6x6x6 e a escala de cinza do xterm
+fn ansi256(rgb: Rgb) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let nearest = |value: u8| {
+        (0..6)
+            .min_by_key(|&i| (LEVELS[i] as i32 - value as i32).abs())
+            .unwrap()
+    };
+    let Rgb(r, g, b) = rgb;
+    let (ri, gi, bi) = (nearest(r), nearest(g), nearest(b));
+    let cube = Rgb(LEVELS[ri], LEVELS[gi], LEVELS[bi]);
+
+    // Os 24 cinzas vão de 8 a 238, de 10 em 10
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray = (average.saturating_sub(3) / 10).min(23) as u8;
+    let level = 8 + 10 * gray;
+
+    if distance(Rgb(level, level, level), rgb) < distance(cube, rgb) {
+        232 + gray
+    } else {
+        16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ansi256, ColorMode};
+    use tetris::interface::palette::Rgb;
+
+    #[test]
+    fn the_mode_comes_from_the_environment() {
+        let detect = ColorMode::from_env;
+        assert_eq!(detect(false, Some("truecolor"), Some("xterm-256color")), ColorMode::TrueColor);
+        assert_eq!(detect(false, None, Some("xterm-256color")), ColorMode::Ansi256);
+        assert_eq!(detect(false, None, Some("linux")), ColorMode::Ansi16);
+        assert_eq!(detect(false, None, Some("dumb")), ColorMode::Ascii);
+        assert_eq!(detect(true, Some("truecolor"), Some("xterm")), ColorMode::Ascii);
+        assert_eq!("256".parse(), Ok(ColorMode::Ansi256));
+        assert!("many".parse::<ColorMode>().is_err());
+    }
+
+    #[test]
+    fn palette_colours_map_to_the_nearest_xterm_colour() {
+        assert_eq!(ansi256(Rgb(255, 0, 0)), 196);
+        assert_eq!(ansi256(Rgb(0, 0, 0)), 16);
+        assert_eq!(ansi256(Rgb(128, 128, 128)), 244);
+        // Fundo do campo, azul escuro
+        assert_eq!(ansi256(Rgb(15, 52, 96)), 23);
+    }
+}
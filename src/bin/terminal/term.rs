@@ -0,0 +1,94 @@
+use crate::layout::draw_frame;
+use crate::render::{Canvas, Renderer as CanvasRenderer};
+use crate::style::ColorMode;
+use crossterm::cursor::{Hide, Show};
+use crossterm::event::{KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags};
+use crossterm::execute;
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use std::io;
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tetris::interface::ui::{Frame, Renderer};
+
+// Estado global para que o hook de pânico saiba o que desfazer
+static RAW: AtomicBool = AtomicBool::new(false);
+static ENHANCED: AtomicBool = AtomicBool::new(false);
+
+fn restore() {
+    if !RAW.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    let mut stdout = io::stdout();
+    if ENHANCED.swap(false, Ordering::SeqCst) {
+        let _ = execute!(stdout, PopKeyboardEnhancementFlags);
+    }
+    let _ = execute!(stdout, Show, LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+}
+
+/// Terminal em modo raw com tela alternativa. O terminal volta ao normal
+/// quando o valor é descartado, inclusive durante um pânico.
+pub struct RawTerminal {
+    mode: ColorMode,
+    reports_releases: bool,
+    renderer: CanvasRenderer,
+}
+
+impl RawTerminal {
+    pub fn enter(mode: ColorMode) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        RAW.store(true, Ordering::SeqCst);
+        // Criado logo após o modo raw: se algo abaixo falhar, o `?` descarta
+        // o valor e o `Drop` restaura o terminal
+        let mut raw = Self {
+            mode,
+            reports_releases: cfg!(windows),
+            renderer: CanvasRenderer::default(),
+        };
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore();
+            hook(info);
+        }));
+
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen, Hide, Clear(ClearType::All))?;
+        // Só o protocolo de teclado do kitty informa quando a tecla é solta
+        let enhanced = terminal::supports_keyboard_enhancement().unwrap_or(false);
+        if enhanced {
+            execute!(
+                stdout,
+                PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+            )?;
+            ENHANCED.store(true, Ordering::SeqCst);
+            raw.reports_releases = true;
+        }
+        Ok(raw)
+    }
+
+    pub fn reports_releases(&self) -> bool {
+        self.reports_releases
+    }
+
+    pub fn invalidate(&mut self) {
+        self.renderer.invalidate();
+    }
+}
+
+impl Renderer for RawTerminal {
+    type Error = io::Error;
+
+    // Desenha no tamanho atual do terminal; só o que mudou é enviado
+    fn render(&mut self, frame: &Frame) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let mut canvas = Canvas::new(width, height);
+        draw_frame(&mut canvas, self.mode, frame);
+        self.renderer.draw(&mut io::stdout().lock(), canvas)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        restore();
+    }
+}
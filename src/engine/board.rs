@@ -0,0 +1,203 @@
+use crate::engine::piece::Cell;
+use crate::engine::position::Pos;
+use crate::engine::shape::Shape;
+
+pub const MAX_WIDTH: u32 = 64;
+// Campos aceitos em arquivos (saves e replays): pelo menos a caixa de 4
+// colunas em que as peças nascem, e no máximo o que o bitboard e uma tela
+// comportam
+pub const MIN_WIDTH: u32 = 4;
+pub const MAX_HEIGHT: u32 = 100;
+
+// Tamanho lido de um arquivo; em u64 para aceitar os varints dos replays
+pub fn is_supported_size(width: u64, height: u64) -> bool {
+    let widths = MIN_WIDTH as u64..=MAX_WIDTH as u64;
+    widths.contains(&width) && (1..=MAX_HEIGHT as u64).contains(&height)
+}
+
+/// Peças travadas no campo. Cada linha é uma máscara de bits (bit `x` = coluna
+/// `x`), de modo que colisão, linha cheia e remoção de linhas custam O(linhas);
+/// a grade paralela `cells` guarda o tipo de cada célula só para as cores.
+///
+/// As linhas visíveis vão de `0` a `height - 1`; as `buffer` linhas ocultas
+/// acima delas usam `y` negativo.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Board {
+    width: i32,
+    height: i32,
+    buffer: i32,
+    rows: Vec<u64>,
+    cells: Vec<Cell>,
+}
+
+impl Board {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self::with_buffer(width, height, 0)
+    }
+
+    pub fn with_buffer(width: u32, height: u32, buffer: u32) -> Self {
+        assert!(width <= MAX_WIDTH, "board is at most {} cells wide", MAX_WIDTH);
+        let total = height + buffer;
+        Self {
+            width: width as i32,
+            height: height as i32,
+            buffer: buffer as i32,
+            rows: vec![0; total as usize],
+            cells: vec![Cell::Empty; (width * total) as usize],
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    pub fn buffer(&self) -> i32 {
+        self.buffer
+    }
+
+    // Linha mais alta da zona oculta
+    pub fn top(&self) -> i32 {
+        -self.buffer
+    }
+
+    fn full_row(&self) -> u64 {
+        u64::MAX.checked_shr(64 - self.width as u32).unwrap_or(0)
+    }
+
+    fn contains(&self, pos: Pos) -> bool {
+        0 <= pos.0 && pos.0 < self.width && self.top() <= pos.1 && pos.1 < self.height
+    }
+
+    fn row_index(&self, y: i32) -> usize {
+        (y + self.buffer) as usize
+    }
+
+    fn index(&self, pos: Pos) -> usize {
+        self.row_index(pos.1) * self.width as usize + pos.0 as usize
+    }
+
+    pub fn row(&self, y: i32) -> u64 {
+        self.rows[self.row_index(y)]
+    }
+
+    // Posições fora do campo nunca estão ocupadas; limites são checados à parte
+    pub fn is_occupied(&self, pos: Pos) -> bool {
+        self.contains(pos) && self.row(pos.1) & (1 << pos.0) != 0
+    }
+
+    pub fn get(&self, pos: Pos) -> Cell {
+        if self.contains(pos) {
+            self.cells[self.index(pos)]
+        } else {
+            Cell::Empty
+        }
+    }
+
+    pub fn set(&mut self, pos: Pos, cell: Cell) {
+        if !self.contains(pos) {
+            return;
+        }
+        let index = self.index(pos);
+        let row = self.row_index(pos.1);
+        self.cells[index] = cell;
+        if cell.is_empty() {
+            self.rows[row] &= !(1 << pos.0);
+        } else {
+            self.rows[row] |= 1 << pos.0;
+        }
+    }
+
+    pub fn collides(&self, shape: &Shape) -> bool {
+        shape.iter_positions().any(|pos| self.is_occupied(pos))
+    }
+
+    pub fn place(&mut self, shape: &Shape) {
+        for pos in shape.iter_positions() {
+            self.set(pos, Cell::Piece(shape.kind()));
+        }
+    }
+
+    pub fn is_line_full(&self, y: i32) -> bool {
+        self.top() <= y && y < self.height && self.row(y) == self.full_row()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.iter().all(|&row| row == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.rows.fill(0);
+        self.cells.fill(Cell::Empty);
+    }
+
+    /// Remove as linhas cheias, descendo as de cima, e devolve quantas saíram.
+    pub fn clear_full_lines(&mut self) -> u32 {
+        let width = self.width as usize;
+        let full_row = self.full_row();
+        let mut write = self.rows.len();
+
+        // Compacta de baixo para cima, pulando as linhas cheias
+        for read in (0..self.rows.len()).rev() {
+            if self.rows[read] == full_row {
+                continue;
+            }
+            write -= 1;
+            if write != read {
+                self.rows[write] = self.rows[read];
+                self.cells
+                    .copy_within(read * width..(read + 1) * width, write * width);
+            }
+        }
+
+        let removed = write;
+        self.rows[..removed].fill(0);
+        self.cells[..removed * width].fill(Cell::Empty);
+        removed as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Board;
+    use crate::engine::piece::{Cell, PieceKind};
+    use crate::engine::position::Pos;
+
+    #[test]
+    fn clearing_lines_moves_rows_above_down() {
+        let mut board = Board::new(3, 4);
+        for x in 0..3 {
+            board.set(Pos(x, 3), Cell::Piece(PieceKind::I));
+            board.set(Pos(x, 1), Cell::Garbage);
+        }
+        board.set(Pos(1, 2), Cell::Piece(PieceKind::T));
+        board.set(Pos(2, 0), Cell::Piece(PieceKind::Z));
+
+        assert_eq!(board.clear_full_lines(), 2);
+        assert_eq!(board.get(Pos(1, 3)), Cell::Piece(PieceKind::T));
+        assert_eq!(board.get(Pos(2, 2)), Cell::Piece(PieceKind::Z));
+        assert_eq!(board.row(3), 0b010);
+        assert_eq!(board.row(2), 0b100);
+        assert_eq!(board.row(0) | board.row(1), 0);
+    }
+
+    #[test]
+    fn buffer_rows_sit_above_the_visible_field() {
+        let mut board = Board::with_buffer(3, 2, 2);
+        assert_eq!(board.top(), -2);
+        board.set(Pos(0, -2), Cell::Garbage);
+        board.set(Pos(0, -3), Cell::Garbage);
+        assert!(board.is_occupied(Pos(0, -2)));
+        assert!(!board.is_occupied(Pos(0, -3)));
+
+        for x in 0..3 {
+            board.set(Pos(x, 1), Cell::Garbage);
+        }
+        assert_eq!(board.clear_full_lines(), 1);
+        assert_eq!(board.get(Pos(0, -1)), Cell::Garbage);
+        assert_eq!(board.row(-2), 0);
+    }
+}
@@ -1,2 +1,8 @@
+pub mod board;
+pub mod piece;
 pub mod shape;
 pub mod position;
+pub mod randomizer;
+pub mod rng;
+pub mod rotation;
+pub mod scoring;
@@ -0,0 +1,103 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+pub enum PieceKind {
+    I,
+    O,
+    T,
+    J,
+    L,
+    S,
+    Z,
+}
+
+impl PieceKind {
+    pub const ALL: [PieceKind; 7] = [
+        PieceKind::I,
+        PieceKind::O,
+        PieceKind::T,
+        PieceKind::J,
+        PieceKind::L,
+        PieceKind::S,
+        PieceKind::Z,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = PieceKind> {
+        Self::ALL.into_iter()
+    }
+
+    pub fn letter(self) -> char {
+        match self {
+            PieceKind::I => 'I',
+            PieceKind::O => 'O',
+            PieceKind::T => 'T',
+            PieceKind::J => 'J',
+            PieceKind::L => 'L',
+            PieceKind::S => 'S',
+            PieceKind::Z => 'Z',
+        }
+    }
+}
+
+impl Display for PieceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.letter())
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParsePieceKindError(String);
+
+impl Display for ParsePieceKindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown piece kind: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParsePieceKindError {}
+
+impl FromStr for PieceKind {
+    type Err = ParsePieceKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::iter()
+            .find(|kind| s.eq_ignore_ascii_case(&kind.to_string()))
+            .ok_or_else(|| ParsePieceKindError(s.to_string()))
+    }
+}
+
+/// Conteúdo de uma célula do campo.
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy, Default)]
+pub enum Cell {
+    #[default]
+    Empty,
+    Piece(PieceKind),
+    Garbage,
+}
+
+impl Cell {
+    pub fn is_empty(self) -> bool {
+        self == Cell::Empty
+    }
+
+    pub fn kind(self) -> Option<PieceKind> {
+        match self {
+            Cell::Piece(kind) => Some(kind),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PieceKind;
+
+    #[test]
+    fn letters_round_trip() {
+        for kind in PieceKind::iter() {
+            assert_eq!(kind.to_string().parse::<PieceKind>(), Ok(kind));
+        }
+        assert!("X".parse::<PieceKind>().is_err());
+    }
+}
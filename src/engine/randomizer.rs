@@ -0,0 +1,209 @@
+use crate::engine::piece::PieceKind;
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+
+/// Fonte da sequência de peças usada por `Tetris`.
+pub trait Randomizer: Debug {
+    fn next_piece(&mut self, rng: &mut dyn RngCore) -> PieceKind;
+
+    // Chamado ao reiniciar o jogo, para descartar bolsas ou históricos antigos
+    fn reset(&mut self) {}
+
+    // Estado em texto para snapshots; `load` faz o caminho inverso
+    fn save(&self) -> String;
+}
+
+/// Reconstrói um randomizer salvo com `Randomizer::save`.
+pub fn load(state: &str) -> Option<Box<dyn Randomizer>> {
+    let mut parts = state.split_whitespace();
+    let randomizer: Box<dyn Randomizer> = match parts.next()? {
+        "random" => Box::new(PureRandom),
+        "bag" => Box::new(Bag {
+            copies: parts.next()?.parse::<usize>().ok()?.max(1),
+            pieces: parse_kinds(parts.next().unwrap_or(""))?,
+        }),
+        "history" => Box::new(History {
+            rolls: parts.next()?.parse::<u32>().ok()?.max(1),
+            history: parse_kinds(parts.next()?)?.into(),
+            first: parts.next()?.parse().ok()?,
+        }),
+        _ => return None,
+    };
+    Some(randomizer)
+}
+
+fn format_kinds<'a>(kinds: impl IntoIterator<Item = &'a PieceKind>) -> String {
+    kinds.into_iter().map(|kind| kind.letter()).collect()
+}
+
+fn parse_kinds(letters: &str) -> Option<Vec<PieceKind>> {
+    letters
+        .chars()
+        .map(|letter| letter.to_string().parse().ok())
+        .collect()
+}
+
+/// Sorteia cada peça de forma independente.
+#[derive(Debug, Default)]
+pub struct PureRandom;
+
+impl Randomizer for PureRandom {
+    fn next_piece(&mut self, rng: &mut dyn RngCore) -> PieceKind {
+        *PieceKind::ALL.choose(rng).unwrap()
+    }
+
+    fn save(&self) -> String {
+        "random".to_string()
+    }
+}
+
+/// Embaralha `copies` exemplares de cada peça e os entrega em sequência.
+#[derive(Debug)]
+pub struct Bag {
+    copies: usize,
+    pieces: Vec<PieceKind>,
+}
+
+impl Bag {
+    pub fn new(copies: usize) -> Self {
+        Self {
+            copies: copies.max(1),
+            pieces: vec![],
+        }
+    }
+
+    pub fn seven() -> Self {
+        Self::new(1)
+    }
+
+    pub fn fourteen() -> Self {
+        Self::new(2)
+    }
+}
+
+impl Randomizer for Bag {
+    fn next_piece(&mut self, rng: &mut dyn RngCore) -> PieceKind {
+        if self.pieces.is_empty() {
+            self.pieces = PieceKind::iter()
+                .flat_map(|kind| std::iter::repeat_n(kind, self.copies))
+                .collect();
+            self.pieces.shuffle(rng);
+        }
+        self.pieces.pop().unwrap()
+    }
+
+    fn reset(&mut self) {
+        self.pieces.clear();
+    }
+
+    fn save(&self) -> String {
+        format!("bag {} {}", self.copies, format_kinds(&self.pieces))
+            .trim_end()
+            .to_string()
+    }
+}
+
+/// Randomizer no estilo TGM: resorteia até `rolls` vezes enquanto a peça
+/// estiver entre as 4 últimas entregues.
+#[derive(Debug)]
+pub struct History {
+    rolls: u32,
+    history: VecDeque<PieceKind>,
+    first: bool,
+}
+
+impl History {
+    pub fn new(rolls: u32) -> Self {
+        Self {
+            rolls: rolls.max(1),
+            history: [PieceKind::Z; 4].into_iter().collect(),
+            first: true,
+        }
+    }
+
+    pub fn tgm() -> Self {
+        Self::new(4)
+    }
+
+    pub fn tgm2() -> Self {
+        Self::new(6)
+    }
+}
+
+impl Randomizer for History {
+    fn next_piece(&mut self, rng: &mut dyn RngCore) -> PieceKind {
+        let kind = if self.first {
+            // A primeira peça nunca é S, Z ou O
+            self.first = false;
+            *[PieceKind::I, PieceKind::T, PieceKind::J, PieceKind::L]
+                .choose(rng)
+                .unwrap()
+        } else {
+            let mut kind = *PieceKind::ALL.choose(rng).unwrap();
+            for _ in 1..self.rolls {
+                if !self.history.contains(&kind) {
+                    break;
+                }
+                kind = *PieceKind::ALL.choose(rng).unwrap();
+            }
+            kind
+        };
+
+        self.history.pop_front();
+        self.history.push_back(kind);
+        kind
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.rolls);
+    }
+
+    fn save(&self) -> String {
+        format!(
+            "history {} {} {}",
+            self.rolls,
+            format_kinds(&self.history),
+            self.first
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load, Bag, History, Randomizer};
+    use crate::engine::rng::GameRng;
+    use std::collections::HashSet;
+
+    #[test]
+    fn seven_bag_deals_every_piece_once_per_bag() {
+        let mut bag = Bag::seven();
+        let mut rng = rand::thread_rng();
+        for _ in 0..3 {
+            let kinds: HashSet<_> = (0..7).map(|_| bag.next_piece(&mut rng)).collect();
+            assert_eq!(kinds.len(), 7);
+        }
+    }
+
+    #[test]
+    fn saved_randomizers_continue_the_same_sequence() {
+        let mut rng = GameRng::new(7);
+        let mut originals: Vec<Box<dyn Randomizer>> =
+            vec![Box::new(Bag::fourteen()), Box::new(History::tgm())];
+        for original in &mut originals {
+            for _ in 0..5 {
+                original.next_piece(&mut rng);
+            }
+            let mut restored = load(&original.save()).unwrap();
+            let mut restored_rng = rng;
+            for _ in 0..20 {
+                assert_eq!(
+                    original.next_piece(&mut rng),
+                    restored.next_piece(&mut restored_rng)
+                );
+            }
+        }
+        assert!(load("bag x").is_none());
+    }
+}
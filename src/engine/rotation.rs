@@ -0,0 +1,117 @@
+use crate::engine::piece::PieceKind;
+use crate::engine::position::Pos;
+
+/// Estado de rotação de uma peça, na convenção SRS (0, R, 2, L).
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+pub enum Rotation {
+    Spawn,
+    Right,
+    Half,
+    Left,
+}
+
+impl Rotation {
+    pub fn cw(self) -> Self {
+        match self {
+            Rotation::Spawn => Rotation::Right,
+            Rotation::Right => Rotation::Half,
+            Rotation::Half => Rotation::Left,
+            Rotation::Left => Rotation::Spawn,
+        }
+    }
+
+    pub fn ccw(self) -> Self {
+        match self {
+            Rotation::Spawn => Rotation::Left,
+            Rotation::Right => Rotation::Spawn,
+            Rotation::Half => Rotation::Right,
+            Rotation::Left => Rotation::Half,
+        }
+    }
+
+    pub fn flipped(self) -> Self {
+        self.cw().cw()
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Rotation::Spawn => 0,
+            Rotation::Right => 1,
+            Rotation::Half => 2,
+            Rotation::Left => 3,
+        }
+    }
+}
+
+// Tabelas SRS com o eixo y já invertido (y cresce para baixo na grade).
+const JLSTZ_CW_KICKS: [[Pos; 5]; 4] = [
+    // 0 -> R
+    [Pos(0, 0), Pos(-1, 0), Pos(-1, -1), Pos(0, 2), Pos(-1, 2)],
+    // R -> 2
+    [Pos(0, 0), Pos(1, 0), Pos(1, 1), Pos(0, -2), Pos(1, -2)],
+    // 2 -> L
+    [Pos(0, 0), Pos(1, 0), Pos(1, -1), Pos(0, 2), Pos(1, 2)],
+    // L -> 0
+    [Pos(0, 0), Pos(-1, 0), Pos(-1, 1), Pos(0, -2), Pos(-1, -2)],
+];
+
+const JLSTZ_CCW_KICKS: [[Pos; 5]; 4] = [
+    // 0 -> L
+    [Pos(0, 0), Pos(1, 0), Pos(1, -1), Pos(0, 2), Pos(1, 2)],
+    // R -> 0
+    [Pos(0, 0), Pos(1, 0), Pos(1, 1), Pos(0, -2), Pos(1, -2)],
+    // 2 -> R
+    [Pos(0, 0), Pos(-1, 0), Pos(-1, -1), Pos(0, 2), Pos(-1, 2)],
+    // L -> 2
+    [Pos(0, 0), Pos(-1, 0), Pos(-1, 1), Pos(0, -2), Pos(-1, -2)],
+];
+
+const I_CW_KICKS: [[Pos; 5]; 4] = [
+    // 0 -> R
+    [Pos(0, 0), Pos(-2, 0), Pos(1, 0), Pos(-2, 1), Pos(1, -2)],
+    // R -> 2
+    [Pos(0, 0), Pos(-1, 0), Pos(2, 0), Pos(-1, -2), Pos(2, 1)],
+    // 2 -> L
+    [Pos(0, 0), Pos(2, 0), Pos(-1, 0), Pos(2, -1), Pos(-1, 2)],
+    // L -> 0
+    [Pos(0, 0), Pos(1, 0), Pos(-2, 0), Pos(1, 2), Pos(-2, -1)],
+];
+
+const I_CCW_KICKS: [[Pos; 5]; 4] = [
+    // 0 -> L
+    [Pos(0, 0), Pos(-1, 0), Pos(2, 0), Pos(-1, -2), Pos(2, 1)],
+    // R -> 0
+    [Pos(0, 0), Pos(2, 0), Pos(-1, 0), Pos(2, -1), Pos(-1, 2)],
+    // 2 -> R
+    [Pos(0, 0), Pos(1, 0), Pos(-2, 0), Pos(1, 2), Pos(-2, -1)],
+    // L -> 2
+    [Pos(0, 0), Pos(-2, 0), Pos(1, 0), Pos(-2, 1), Pos(1, -2)],
+];
+
+// O SRS não define giro de 180°; usamos a tabela do SRS+ para todas as peças
+const HALF_KICKS: [[Pos; 6]; 4] = [
+    // 0 -> 2
+    [Pos(0, 0), Pos(0, -1), Pos(1, -1), Pos(-1, -1), Pos(1, 0), Pos(-1, 0)],
+    // R -> L
+    [Pos(0, 0), Pos(1, 0), Pos(1, -2), Pos(1, -1), Pos(0, -2), Pos(0, -1)],
+    // 2 -> 0
+    [Pos(0, 0), Pos(0, 1), Pos(-1, 1), Pos(1, 1), Pos(-1, 0), Pos(1, 0)],
+    // L -> R
+    [Pos(0, 0), Pos(-1, 0), Pos(-1, -2), Pos(-1, -1), Pos(0, -2), Pos(0, -1)],
+];
+
+const NO_KICKS: [Pos; 1] = [Pos(0, 0)];
+
+/// Deslocamentos testados, em ordem, ao girar uma peça de `from` para `to`.
+pub fn kicks(kind: PieceKind, from: Rotation, to: Rotation) -> &'static [Pos] {
+    let i = from.index();
+    match kind {
+        PieceKind::O => &NO_KICKS,
+        _ if to == from => &NO_KICKS,
+        _ if to == from.flipped() => &HALF_KICKS[i],
+        PieceKind::I if to == from.cw() => &I_CW_KICKS[i],
+        PieceKind::I => &I_CCW_KICKS[i],
+        _ if to == from.cw() => &JLSTZ_CW_KICKS[i],
+        _ => &JLSTZ_CCW_KICKS[i],
+    }
+}
@@ -0,0 +1,279 @@
+use std::fmt::{self, Debug, Display};
+
+/// Resultado da regra dos 3 cantos ao travar uma peça T.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Spin {
+    None,
+    Mini,
+    Full,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ClearKind {
+    Single,
+    Double,
+    Triple,
+    Tetris,
+    MiniTSpin,
+    MiniTSpinSingle,
+    MiniTSpinDouble,
+    TSpin,
+    TSpinSingle,
+    TSpinDouble,
+    TSpinTriple,
+}
+
+impl ClearKind {
+    // `None` quando a peça travou sem limpar linhas e sem T-spin
+    pub fn new(lines: u32, spin: Spin) -> Option<Self> {
+        match (spin, lines) {
+            (Spin::None, 1) => Some(ClearKind::Single),
+            (Spin::None, 2) => Some(ClearKind::Double),
+            (Spin::None, 3) => Some(ClearKind::Triple),
+            (Spin::None, 4) => Some(ClearKind::Tetris),
+            (Spin::None, _) => None,
+            (Spin::Mini, 0) => Some(ClearKind::MiniTSpin),
+            (Spin::Mini, 1) => Some(ClearKind::MiniTSpinSingle),
+            (Spin::Mini, 2) => Some(ClearKind::MiniTSpinDouble),
+            (_, 0) => Some(ClearKind::TSpin),
+            (_, 1) => Some(ClearKind::TSpinSingle),
+            (_, 2) => Some(ClearKind::TSpinDouble),
+            (_, 3) => Some(ClearKind::TSpinTriple),
+            _ => None,
+        }
+    }
+
+    pub fn lines(self) -> u32 {
+        match self {
+            ClearKind::MiniTSpin | ClearKind::TSpin => 0,
+            ClearKind::Single | ClearKind::MiniTSpinSingle | ClearKind::TSpinSingle => 1,
+            ClearKind::Double | ClearKind::MiniTSpinDouble | ClearKind::TSpinDouble => 2,
+            ClearKind::Triple | ClearKind::TSpinTriple => 3,
+            ClearKind::Tetris => 4,
+        }
+    }
+
+    pub fn spin(self) -> Spin {
+        match self {
+            ClearKind::Single | ClearKind::Double | ClearKind::Triple | ClearKind::Tetris => {
+                Spin::None
+            }
+            ClearKind::MiniTSpin | ClearKind::MiniTSpinSingle | ClearKind::MiniTSpinDouble => {
+                Spin::Mini
+            }
+            _ => Spin::Full,
+        }
+    }
+
+    // Limpezas "difíceis" mantêm a sequência de back-to-back
+    pub fn is_difficult(self) -> bool {
+        self.lines() > 0 && (self == ClearKind::Tetris || self.spin() != Spin::None)
+    }
+}
+
+impl Display for ClearKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ClearKind::Single => "Single",
+            ClearKind::Double => "Double",
+            ClearKind::Triple => "Triple",
+            ClearKind::Tetris => "Tetris",
+            ClearKind::MiniTSpin => "Mini T-Spin",
+            ClearKind::MiniTSpinSingle => "Mini T-Spin Single",
+            ClearKind::MiniTSpinDouble => "Mini T-Spin Double",
+            ClearKind::TSpin => "T-Spin",
+            ClearKind::TSpinSingle => "T-Spin Single",
+            ClearKind::TSpinDouble => "T-Spin Double",
+            ClearKind::TSpinTriple => "T-Spin Triple",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Contexto de uma limpeza de linhas, montado por `Tetris` ao travar a peça.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Clear {
+    pub kind: ClearKind,
+    pub level: u32,
+    // Quantas limpezas seguidas vieram antes desta (0 na primeira)
+    pub combo: u32,
+    pub back_to_back: bool,
+    // O campo ficou vazio depois desta limpeza
+    pub perfect_clear: bool,
+}
+
+/// Pontos de uma limpeza, separados para que os front-ends possam exibi-los.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ScoreBreakdown {
+    pub kind: ClearKind,
+    pub base: u32,
+    pub combo: u32,
+    pub combo_bonus: u32,
+    pub back_to_back: bool,
+    pub perfect_clear: bool,
+    pub perfect_clear_bonus: u32,
+    pub total: u32,
+}
+
+impl Display for ScoreBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.back_to_back {
+            write!(f, "Back-to-Back ")?;
+        }
+        write!(f, "{} +{}", self.kind, self.total)?;
+        if self.combo_bonus > 0 {
+            write!(f, " (Combo x{})", self.combo)?;
+        }
+        if self.perfect_clear {
+            write!(f, " PERFECT CLEAR")?;
+        }
+        Ok(())
+    }
+}
+
+pub trait ScoringRule: Debug {
+    // Identifica a regra em snapshots (veja `rule_by_name`)
+    fn name(&self) -> &'static str;
+
+    fn clear(&self, clear: &Clear) -> ScoreBreakdown;
+
+    fn soft_drop(&self, _cells: u32) -> u32 {
+        0
+    }
+
+    fn hard_drop(&self, _cells: u32) -> u32 {
+        0
+    }
+}
+
+pub fn rule_by_name(name: &str) -> Option<Box<dyn ScoringRule>> {
+    match name {
+        "classic" => Some(Box::new(Classic)),
+        "guideline" => Some(Box::new(Guideline)),
+        _ => None,
+    }
+}
+
+/// Tabela fixa original: 100/300/500/800, sem nível, combo, back-to-back ou T-spins.
+#[derive(Debug, Default)]
+pub struct Classic;
+
+impl ScoringRule for Classic {
+    fn name(&self) -> &'static str {
+        "classic"
+    }
+
+    fn clear(&self, clear: &Clear) -> ScoreBreakdown {
+        let base = match clear.kind.lines() {
+            1 => 100,
+            2 => 300,
+            3 => 500,
+            4 => 800,
+            _ => 0,
+        };
+        ScoreBreakdown {
+            kind: clear.kind,
+            base,
+            combo: clear.combo,
+            combo_bonus: 0,
+            back_to_back: false,
+            perfect_clear: clear.perfect_clear,
+            perfect_clear_bonus: 0,
+            total: base,
+        }
+    }
+}
+
+/// Pontuação do guideline: multiplicador de nível, T-spins, combos,
+/// back-to-back (x1.5), perfect clears e pontos por soft drop (1 por célula)
+/// e hard drop (2 por célula).
+#[derive(Debug, Default)]
+pub struct Guideline;
+
+impl ScoringRule for Guideline {
+    fn name(&self) -> &'static str {
+        "guideline"
+    }
+
+    fn clear(&self, clear: &Clear) -> ScoreBreakdown {
+        let mut base = match clear.kind {
+            ClearKind::Single => 100,
+            ClearKind::Double => 300,
+            ClearKind::Triple => 500,
+            ClearKind::Tetris => 800,
+            ClearKind::MiniTSpin => 100,
+            ClearKind::MiniTSpinSingle => 200,
+            ClearKind::MiniTSpinDouble => 400,
+            ClearKind::TSpin => 400,
+            ClearKind::TSpinSingle => 800,
+            ClearKind::TSpinDouble => 1200,
+            ClearKind::TSpinTriple => 1600,
+        } * clear.level;
+        if clear.back_to_back {
+            base = base * 3 / 2;
+        }
+        let combo_bonus = 50 * clear.combo * clear.level;
+        let perfect_clear_bonus = match (clear.perfect_clear, clear.kind.lines()) {
+            (false, _) => 0,
+            (true, 4) if clear.back_to_back => 3200,
+            (true, 1) => 800,
+            (true, 2) => 1200,
+            (true, 3) => 1800,
+            (true, _) => 2000,
+        } * clear.level;
+
+        ScoreBreakdown {
+            kind: clear.kind,
+            base,
+            combo: clear.combo,
+            combo_bonus,
+            back_to_back: clear.back_to_back,
+            perfect_clear: clear.perfect_clear,
+            perfect_clear_bonus,
+            total: base + combo_bonus + perfect_clear_bonus,
+        }
+    }
+
+    fn soft_drop(&self, cells: u32) -> u32 {
+        cells
+    }
+
+    fn hard_drop(&self, cells: u32) -> u32 {
+        2 * cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Classic, Clear, ClearKind, Guideline, ScoringRule};
+
+    #[test]
+    fn guideline_applies_level_back_to_back_and_combo() {
+        let tetris = Clear {
+            kind: ClearKind::Tetris,
+            level: 1,
+            combo: 0,
+            back_to_back: true,
+            perfect_clear: false,
+        };
+        assert_eq!(Guideline.clear(&tetris).to_string(), "Back-to-Back Tetris +1200");
+
+        let perfect = Clear {
+            perfect_clear: true,
+            ..tetris
+        };
+        assert_eq!(Guideline.clear(&perfect).total, 1200 + 3200);
+
+        let double = Clear {
+            kind: ClearKind::Double,
+            level: 3,
+            combo: 2,
+            back_to_back: false,
+            perfect_clear: false,
+        };
+        let breakdown = Guideline.clear(&double);
+        assert_eq!(breakdown.base, 900);
+        assert_eq!(breakdown.combo_bonus, 300);
+        assert_eq!(Classic.clear(&double).total, 300);
+    }
+}
@@ -1,22 +1,26 @@
+use crate::engine::piece::PieceKind;
 use crate::engine::position::Pos;
-use std::collections::HashSet;
+use crate::engine::rotation::Rotation;
 use std::ops::Add;
 
 #[derive(Debug, Clone)]
 pub struct Shape {
-    typ: &'static str,
-    positions: HashSet<Pos>,
-    anchor: Pos,
+    kind: PieceKind,
+    positions: [Pos; 4],
+    // Centro de rotação em meias células, para que I e O girem em torno de um canto
+    pivot: Pos,
+    rotation: Rotation,
 }
 
 macro_rules! impl_shape_constructor {
-    ($( $new:ident $typ:literal: [ $( $pos:expr ),* ] anchored at $anchor:expr; )*) => {
+    ($( $new:ident $kind:ident: [ $( $pos:expr ),* ] pivoted at $pivot:expr; )*) => {
         $(
             pub fn $new() -> Self {
                 Self {
-                    typ: $typ,
-                    positions: [$( $pos ),*].into_iter().collect(),
-                    anchor: $anchor,
+                    kind: PieceKind::$kind,
+                    positions: [$( $pos ),*],
+                    pivot: $pivot,
+                    rotation: Rotation::Spawn,
                 }
             }
         )*
@@ -25,27 +29,25 @@ macro_rules! impl_shape_constructor {
 
 impl Shape {
     impl_shape_constructor! {
-        new_i "I": [Pos(0,0), Pos(1,0), Pos(2,0), Pos(3,0)] anchored at Pos(1, 0);
-        new_o "O": [Pos(0,0), Pos(1,0), Pos(0,1), Pos(1,1)] anchored at Pos(0, 0);
-        new_t "T": [Pos(0,0), Pos(1,0), Pos(2,0), Pos(1,1)] anchored at Pos(0, 0);
-        new_j "J": [Pos(0,0), Pos(0,1), Pos(0,2), Pos(-1,2)] anchored at Pos(0, 1);
-        new_l "L": [Pos(0,0), Pos(0,1), Pos(0,2), Pos(1,2)] anchored at Pos(0, 1);
-        new_s "S": [Pos(0,0), Pos(1,0), Pos(0,1), Pos(-1,1)] anchored at Pos(0, 0);
-        new_z "Z": [Pos(0,0), Pos(-1,0), Pos(0,1), Pos(-1,1)] anchored at Pos(0, 0);
+        new_i I: [Pos(0,1), Pos(1,1), Pos(2,1), Pos(3,1)] pivoted at Pos(3, 3);
+        new_o O: [Pos(1,0), Pos(2,0), Pos(1,1), Pos(2,1)] pivoted at Pos(3, 1);
+        new_t T: [Pos(1,0), Pos(0,1), Pos(1,1), Pos(2,1)] pivoted at Pos(2, 2);
+        new_j J: [Pos(0,0), Pos(0,1), Pos(1,1), Pos(2,1)] pivoted at Pos(2, 2);
+        new_l L: [Pos(2,0), Pos(0,1), Pos(1,1), Pos(2,1)] pivoted at Pos(2, 2);
+        new_s S: [Pos(1,0), Pos(2,0), Pos(0,1), Pos(1,1)] pivoted at Pos(2, 2);
+        new_z Z: [Pos(0,0), Pos(1,0), Pos(1,1), Pos(2,1)] pivoted at Pos(2, 2);
     }
 
-    pub fn new_rand() -> Self {
-        let random = (rand::random::<f64>() * 7.0).floor() as u8;
-
-        match random {
-            0 => Self::new_i(),
-            1 => Self::new_o(),
-            2 => Self::new_t(),
-            3 => Self::new_j(),
-            4 => Self::new_l(),
-            5 => Self::new_s(),
-            6 => Self::new_z(),
-            _ => unreachable!(),
+    // Peça na orientação de nascimento, dentro da sua caixa
+    pub fn new(kind: PieceKind) -> Self {
+        match kind {
+            PieceKind::I => Self::new_i(),
+            PieceKind::O => Self::new_o(),
+            PieceKind::T => Self::new_t(),
+            PieceKind::J => Self::new_j(),
+            PieceKind::L => Self::new_l(),
+            PieceKind::S => Self::new_s(),
+            PieceKind::Z => Self::new_z(),
         }
     }
 
@@ -53,45 +55,70 @@ impl Shape {
         self.positions.iter().copied()
     }
 
-    pub fn collides_with(&self, other: &Shape) -> bool {
-        self.positions.intersection(&other.positions).count() > 0
+    pub fn kind(&self) -> PieceKind {
+        self.kind
     }
 
-    pub fn typ(&self) -> &'static str {
-        self.typ
+    // Célula central da rotação (exata para J, L, S, T e Z)
+    pub fn center(&self) -> Pos {
+        Pos(self.pivot.0 / 2, self.pivot.1 / 2)
+    }
+
+    pub fn rotation(&self) -> Rotation {
+        self.rotation
     }
 
     pub fn rotated(&self) -> Self {
-        let Pos(a, b) = self.anchor;
+        let Pos(a, b) = self.pivot;
         Self {
-            typ: self.typ, 
-            positions: self
-                .iter_positions()
-                .map(|Pos(x, y)| Pos(-y + b + a, x - a + b))
-                .collect(),
-            anchor: self.anchor,
+            kind: self.kind,
+            positions: self.positions.map(|Pos(x, y)| Pos((a + b) / 2 - y, (b - a) / 2 + x)),
+            pivot: self.pivot,
+            rotation: self.rotation.cw(),
         }
     }
 
-    pub fn remove_line(&mut self, y: i32) {
-        self.positions = self
-            .positions
-            .iter()
-            .copied()
-            .filter(|pos| pos.1 != y)
-            .map(|pos| {
-                if pos.1 >= y {
-                    pos
-                } else {
-                    Pos(pos.0, pos.1 + 1)
-                }
-            })
-            .collect()
+    pub fn rotated_ccw(&self) -> Self {
+        let Pos(a, b) = self.pivot;
+        Self {
+            kind: self.kind,
+            positions: self.positions.map(|Pos(x, y)| Pos((a - b) / 2 + y, (a + b) / 2 - x)),
+            pivot: self.pivot,
+            rotation: self.rotation.ccw(),
+        }
+    }
+
+    pub fn rotated_180(&self) -> Self {
+        let Pos(a, b) = self.pivot;
+        Self {
+            kind: self.kind,
+            positions: self.positions.map(|Pos(x, y)| Pos(a - x, b - y)),
+            pivot: self.pivot,
+            rotation: self.rotation.flipped(),
+        }
     }
 
     pub fn has_position(&self, pos: Pos) -> bool {
         self.positions.contains(&pos)
     }
+
+    // Deslocamento desde a caixa de nascimento; girar não move o pivô
+    pub fn offset(&self) -> Pos {
+        let spawn_pivot = Shape::new(self.kind).pivot;
+        Pos(
+            (self.pivot.0 - spawn_pivot.0) / 2,
+            (self.pivot.1 - spawn_pivot.1) / 2,
+        )
+    }
+
+    // Inverso de `rotation` e `offset`
+    pub fn placed(kind: PieceKind, rotation: Rotation, offset: Pos) -> Self {
+        let mut shape = Shape::new(kind);
+        while shape.rotation != rotation {
+            shape = shape.rotated();
+        }
+        &shape + offset
+    }
 }
 
 impl Add<Pos> for &Shape {
@@ -99,9 +126,10 @@ impl Add<Pos> for &Shape {
 
     fn add(self, rhs: Pos) -> Self::Output {
         Shape {
-            typ: self.typ,
-            positions: self.positions.iter().map(|pos| *pos + rhs).collect(),
-            anchor: self.anchor + rhs,
+            kind: self.kind,
+            positions: self.positions.map(|pos| pos + rhs),
+            pivot: self.pivot + Pos(2 * rhs.0, 2 * rhs.1),
+            rotation: self.rotation,
         }
     }
 }
@@ -0,0 +1,24 @@
+use crate::engine::piece::PieceKind;
+use crate::engine::rotation::Rotation;
+use crate::engine::scoring::{ClearKind, ScoreBreakdown, Spin};
+use crate::interface::tetris::{Direction, GameOverReason};
+
+/// O que aconteceu dentro de `Tetris`, na ordem em que aconteceu. Os eventos
+/// ficam numa fila até o front-end chamar `Tetris::drain_events`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum GameEvent {
+    PieceSpawned { kind: PieceKind },
+    Moved { direction: Direction },
+    Rotated { rotation: Rotation, kick: usize },
+    SoftDropped,
+    HardDropped { cells: u32 },
+    Locked { kind: PieceKind, spin: Spin },
+    // Linhas na ordem de cima para baixo, antes de o campo descer
+    LinesCleared { rows: Vec<i32>, kind: ClearKind },
+    Scored(ScoreBreakdown),
+    Hold { kind: PieceKind },
+    LevelUp { level: u32 },
+    GameOver { reason: GameOverReason },
+    // A partida recomeçou do zero com `Tetris::reset`
+    Reset,
+}
@@ -0,0 +1,267 @@
+use crate::interface::snapshot::{Snapshot, SnapshotError};
+use crate::interface::tetris::{Direction, Tetris};
+use std::fs;
+use std::time::Duration;
+
+// Um frame de jogo, a 60 fps
+pub const FRAME: Duration = Duration::from_micros(16_667);
+
+/// Ação do jogador que muda o estado do jogo.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Input {
+    ShiftLeft,
+    ShiftRight,
+    RotateCw,
+    RotateCcw,
+    Rotate180,
+    SoftDrop,
+    HardDrop,
+    Hold,
+    Reset,
+}
+
+impl Input {
+    pub const ALL: [Input; 9] = [
+        Input::ShiftLeft,
+        Input::ShiftRight,
+        Input::RotateCw,
+        Input::RotateCcw,
+        Input::Rotate180,
+        Input::SoftDrop,
+        Input::HardDrop,
+        Input::Hold,
+        Input::Reset,
+    ];
+
+    pub fn apply(self, tetris: &mut Tetris) {
+        match self {
+            Input::ShiftLeft => tetris.shift(Direction::Left),
+            Input::ShiftRight => tetris.shift(Direction::Right),
+            Input::RotateCw => tetris.rotate(),
+            Input::RotateCcw => tetris.rotate_ccw(),
+            Input::Rotate180 => tetris.rotate_180(),
+            Input::SoftDrop => tetris.soft_drop(),
+            Input::HardDrop => tetris.drop(),
+            Input::Hold => tetris.hold(),
+            Input::Reset => tetris.reset(),
+        }
+    }
+}
+
+/// Avança o jogo em frames discretos, sem relógio: cada `step` aplica as
+/// entradas do frame e depois o lock delay e a gravidade de um `FRAME`.
+/// Front-ends, replays, testes e bots compartilham assim o mesmo tempo; os
+/// front-ends convertem o tempo real em frames com `advance`.
+#[derive(Debug)]
+pub struct Game {
+    tetris: Tetris,
+    frame: u64,
+    // Frações de célula de gravidade acumuladas entre frames
+    gravity_progress: f64,
+    // Tempo real passado que ainda não completou um frame
+    lag: Duration,
+}
+
+impl Game {
+    pub fn new(tetris: Tetris) -> Self {
+        Self {
+            tetris,
+            frame: 0,
+            gravity_progress: 0.0,
+            lag: Duration::ZERO,
+        }
+    }
+
+    pub fn with_seed(width: u32, height: u32, seed: u64) -> Self {
+        Self::new(Tetris::with_seed(width, height, seed))
+    }
+
+    pub fn tetris(&self) -> &Tetris {
+        &self.tetris
+    }
+
+    pub fn tetris_mut(&mut self) -> &mut Tetris {
+        &mut self.tetris
+    }
+
+    pub fn into_tetris(self) -> Tetris {
+        self.tetris
+    }
+
+    // Frames simulados desde o início
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    // Snapshot do `Tetris` mais o relógio, para o tempo continuar contando
+    pub fn snapshot(&self) -> Snapshot {
+        let mut snapshot = self.tetris.snapshot();
+        snapshot.set("frame", self.frame);
+        snapshot.set("gravity_progress", self.gravity_progress);
+        snapshot
+    }
+
+    // Saves sem relógio (anteriores a ele) continuam do frame 0
+    pub fn restore(snapshot: &Snapshot) -> Result<Self, SnapshotError> {
+        let mut game = Self::new(Tetris::restore(snapshot)?);
+        if snapshot.get("frame").is_ok() {
+            game.frame = snapshot.parse("frame")?;
+            game.gravity_progress = snapshot.parse("gravity_progress")?;
+        }
+        Ok(game)
+    }
+
+    // Lê a partida salva em `path`, se houver, e apaga o arquivo. Uma partida
+    // já encerrada não tem o que continuar e também é descartada
+    pub fn load_saved(path: &str) -> Result<Option<Self>, SnapshotError> {
+        let Ok(saved) = fs::read_to_string(path) else {
+            return Ok(None);
+        };
+        let _ = fs::remove_file(path);
+        let game = Self::restore(&saved.parse()?)?;
+        if game.tetris.is_game_over() {
+            return Err(SnapshotError::GameOver);
+        }
+        Ok(Some(game))
+    }
+
+    pub fn step(&mut self, inputs: &[Input]) {
+        // Reiniciar zera também o relógio; o frame do reinício só recomeça a
+        // partida, e o primeiro frame simulado da nova é o próximo
+        if inputs.contains(&Input::Reset) {
+            self.tetris.reset();
+            self.frame = 0;
+            self.gravity_progress = 0.0;
+            return;
+        }
+
+        for &input in inputs {
+            input.apply(&mut self.tetris);
+        }
+
+        self.tetris.update(FRAME);
+        self.gravity_progress += self.tetris.gravity();
+        while self.gravity_progress >= 1.0 {
+            self.tetris.tick();
+            self.gravity_progress -= 1.0;
+        }
+        self.frame += 1;
+    }
+
+    // Passo fixo: simula quantos frames couberem no tempo real passado, e o
+    // resto fica para a próxima chamada. `frame_inputs` é chamado antes de
+    // cada frame; devolve as entradas aplicadas em cada um, para gravar
+    pub fn advance(
+        &mut self,
+        elapsed: Duration,
+        mut frame_inputs: impl FnMut(&Tetris) -> Vec<Input>,
+    ) -> Vec<Vec<Input>> {
+        self.lag += elapsed;
+        let mut frames = Vec::new();
+        while self.lag >= FRAME {
+            let inputs = frame_inputs(&self.tetris);
+            self.step(&inputs);
+            frames.push(inputs);
+            self.lag -= FRAME;
+        }
+        frames
+    }
+
+    // Quanto falta para `advance` simular o próximo frame
+    pub fn until_next_frame(&self) -> Duration {
+        FRAME.saturating_sub(self.lag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Game, Input, FRAME};
+    use crate::interface::snapshot::Snapshot;
+
+    fn bot_game(seed: u64) -> Game {
+        let mut game = Game::with_seed(10, 20, seed);
+        for frame in 0..3000u64 {
+            let inputs: &[Input] = match frame % 11 {
+                0 => &[Input::RotateCw, Input::ShiftLeft],
+                5 => &[Input::ShiftRight, Input::ShiftRight],
+                10 if frame % 3 == 0 => &[Input::HardDrop],
+                _ => &[],
+            };
+            game.step(inputs);
+            game.tetris_mut().drain_events().for_each(drop);
+        }
+        game
+    }
+
+    #[test]
+    fn headless_games_are_deterministic() {
+        for seed in 0..20 {
+            let game = bot_game(seed);
+            assert_eq!(game.frame(), 3000);
+            assert!(game.tetris().statistics().pieces > 0);
+            assert_eq!(game.tetris().snapshot(), bot_game(seed).tetris().snapshot());
+        }
+    }
+
+    #[test]
+    fn restored_games_keep_the_clock() {
+        let mut game = bot_game(3);
+        let saved: Snapshot = game.snapshot().to_string().parse().unwrap();
+        let mut restored = Game::restore(&saved).unwrap();
+        assert_eq!(restored.frame(), 3000);
+
+        for _ in 0..100 {
+            game.step(&[]);
+            restored.step(&[]);
+        }
+        assert_eq!(restored.snapshot(), game.snapshot());
+
+        // Save antigo, só com o `Tetris`
+        let old = Game::restore(&game.tetris().snapshot()).unwrap();
+        assert_eq!(old.frame(), 0);
+    }
+
+    #[test]
+    fn real_time_is_simulated_in_whole_frames() {
+        let mut game = Game::with_seed(10, 20, 1);
+        let mut pressed = vec![Input::ShiftLeft];
+        let mut take = |_: &_| std::mem::take(&mut pressed);
+
+        let frames = game.advance(FRAME * 5 / 2, &mut take);
+        assert_eq!(frames, [vec![Input::ShiftLeft], vec![]]);
+        assert_eq!(game.until_next_frame(), FRAME / 2);
+        assert_eq!(game.advance(FRAME / 2, &mut take).len(), 1);
+        assert_eq!(game.frame(), 3);
+    }
+
+    #[test]
+    fn resetting_restarts_the_clock() {
+        let mut game = Game::with_seed(10, 20, 1);
+        for _ in 0..90 {
+            game.step(&[]);
+        }
+        game.step(&[Input::ShiftLeft, Input::Reset]);
+        assert_eq!(game.frame(), 0);
+        assert_eq!(game.tetris().lines_cleared(), 0);
+
+        // Mesma gravidade de uma partida nova: uma linha a cada 60 frames
+        let start = game.tetris().current_piece().offset();
+        for _ in 0..59 {
+            game.step(&[]);
+        }
+        assert_eq!(game.tetris().current_piece().offset(), start);
+        game.step(&[]);
+        assert_eq!(game.tetris().current_piece().offset().1, start.1 + 1);
+    }
+
+    #[test]
+    fn gravity_moves_the_piece_without_inputs() {
+        let mut game = Game::with_seed(10, 20, 1);
+        let start = game.tetris().current_piece().offset();
+        // Nível 1: uma linha a cada 60 frames
+        for _ in 0..60 {
+            game.step(&[]);
+        }
+        assert_eq!(game.tetris().current_piece().offset().1, start.1 + 1);
+    }
+}
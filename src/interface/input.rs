@@ -0,0 +1,269 @@
+use crate::interface::game::Input;
+use crate::interface::tetris::{Direction, Tetris};
+
+/// Teclas que contam enquanto ficam apertadas.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Button {
+    Left,
+    Right,
+    SoftDrop,
+}
+
+/// O que fazer com esquerda e direita apertadas ao mesmo tempo.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum DirectionPriority {
+    // Vale a última apertada; ao soltá-la, a outra volta a carregar o DAS
+    #[default]
+    LastPressed,
+    // As duas se anulam
+    Cancel,
+}
+
+/// Tempos de repetição, em frames de `game::FRAME`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Handling {
+    // Delayed Auto Shift: espera antes de a peça começar a deslizar
+    pub das: u32,
+    // Auto Repeat Rate: frames entre deslizes; 0 leva direto à parede
+    pub arr: u32,
+    // Soft drop cai `soft_drop_factor` vezes mais rápido que a gravidade
+    pub soft_drop_factor: u32,
+    pub priority: DirectionPriority,
+}
+
+impl Default for Handling {
+    fn default() -> Self {
+        Self {
+            das: 10,
+            arr: 2,
+            soft_drop_factor: 20,
+            priority: DirectionPriority::LastPressed,
+        }
+    }
+}
+
+/// Transforma teclas seguradas em `Input`s por frame, sem depender da
+/// repetição de teclas do sistema operacional.
+#[derive(Debug, Default)]
+pub struct InputHandler {
+    handling: Handling,
+    left: bool,
+    right: bool,
+    soft_drop: bool,
+    last_direction: Option<Direction>,
+    // Há um deslize inicial pendente desde o último aperto
+    tap: bool,
+    das_frames: u32,
+    arr_frames: u32,
+    soft_drop_progress: f64,
+}
+
+impl InputHandler {
+    pub fn new(handling: Handling) -> Self {
+        Self {
+            handling,
+            ..Self::default()
+        }
+    }
+
+    pub fn handling(&self) -> &Handling {
+        &self.handling
+    }
+
+    pub fn set_handling(&mut self, handling: Handling) {
+        self.handling = handling;
+    }
+
+    pub fn is_held(&self, button: Button) -> bool {
+        match button {
+            Button::Left => self.left,
+            Button::Right => self.right,
+            Button::SoftDrop => self.soft_drop,
+        }
+    }
+
+    // Repetições do mesmo aperto são ignoradas
+    pub fn press(&mut self, button: Button) {
+        if self.is_held(button) {
+            return;
+        }
+
+        match button {
+            Button::Left => self.press_direction(Direction::Left),
+            Button::Right => self.press_direction(Direction::Right),
+            Button::SoftDrop => {
+                self.soft_drop = true;
+                // A primeira célula cai já no próximo frame
+                self.soft_drop_progress = 1.0;
+            }
+        }
+    }
+
+    pub fn release(&mut self, button: Button) {
+        match button {
+            Button::Left => self.left = false,
+            Button::Right => self.right = false,
+            Button::SoftDrop => {
+                self.soft_drop = false;
+                self.soft_drop_progress = 0.0;
+            }
+        }
+        if button != Button::SoftDrop {
+            self.tap = false;
+            self.charge();
+        }
+    }
+
+    pub fn release_all(&mut self) {
+        for button in [Button::Left, Button::Right, Button::SoftDrop] {
+            self.release(button);
+        }
+    }
+
+    fn press_direction(&mut self, direction: Direction) {
+        match direction {
+            Direction::Left => self.left = true,
+            Direction::Right => self.right = true,
+        }
+        self.last_direction = Some(direction);
+        self.tap = self.direction().is_some();
+        self.charge();
+    }
+
+    // Recomeça a carregar o DAS
+    fn charge(&mut self) {
+        self.das_frames = 0;
+        self.arr_frames = 0;
+    }
+
+    // Direção que está valendo agora, pelas regras de prioridade
+    pub fn direction(&self) -> Option<Direction> {
+        match (self.left, self.right) {
+            (true, false) => Some(Direction::Left),
+            (false, true) => Some(Direction::Right),
+            (false, false) => None,
+            (true, true) => match self.handling.priority {
+                DirectionPriority::LastPressed => self.last_direction,
+                DirectionPriority::Cancel => None,
+            },
+        }
+    }
+
+    /// Entradas deste frame; chame uma vez antes de cada `Game::step`.
+    pub fn frame_inputs(&mut self, tetris: &Tetris) -> Vec<Input> {
+        let mut inputs = Vec::new();
+
+        if let Some(direction) = self.direction() {
+            let shift = match direction {
+                Direction::Left => Input::ShiftLeft,
+                Direction::Right => Input::ShiftRight,
+            };
+            // ARR 0 repete o deslize até atravessar o campo inteiro
+            let repeats = match self.handling.arr {
+                0 => tetris.width() as usize,
+                _ => 1,
+            };
+
+            if self.tap {
+                self.tap = false;
+                inputs.push(shift);
+            } else if self.das_frames < self.handling.das {
+                self.das_frames += 1;
+                if self.das_frames == self.handling.das {
+                    inputs.extend(std::iter::repeat_n(shift, repeats));
+                }
+            } else {
+                self.arr_frames += 1;
+                if self.arr_frames >= self.handling.arr {
+                    self.arr_frames = 0;
+                    inputs.extend(std::iter::repeat_n(shift, repeats));
+                }
+            }
+        }
+
+        if self.soft_drop {
+            let cells_per_frame = tetris.gravity() * self.handling.soft_drop_factor as f64;
+            self.soft_drop_progress += cells_per_frame;
+            // Sem sentido cair mais que a altura do campo num frame
+            let cells = (self.soft_drop_progress.floor() as i32).min(tetris.height());
+            self.soft_drop_progress -= self.soft_drop_progress.floor();
+            inputs.extend(std::iter::repeat_n(Input::SoftDrop, cells.max(0) as usize));
+        }
+
+        inputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Button, DirectionPriority, Handling, InputHandler};
+    use crate::interface::game::Input;
+    use crate::interface::tetris::{Direction, Tetris};
+
+    fn shifts(handler: &mut InputHandler, tetris: &Tetris, frames: usize) -> Vec<usize> {
+        (0..frames)
+            .map(|_| {
+                handler
+                    .frame_inputs(tetris)
+                    .iter()
+                    .filter(|&&input| input == Input::ShiftLeft || input == Input::ShiftRight)
+                    .count()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn das_delays_the_repeat_and_arr_paces_it() {
+        let tetris = Tetris::with_seed(10, 20, 0);
+        let mut handler = InputHandler::new(Handling {
+            das: 3,
+            arr: 2,
+            ..Handling::default()
+        });
+        handler.press(Button::Left);
+        handler.press(Button::Left); // Repetição do SO
+        assert_eq!(shifts(&mut handler, &tetris, 8), [1, 0, 0, 1, 0, 1, 0, 1]);
+
+        handler.release(Button::Left);
+        assert_eq!(shifts(&mut handler, &tetris, 2), [0, 0]);
+
+        handler.set_handling(Handling {
+            das: 1,
+            arr: 0,
+            ..Handling::default()
+        });
+        handler.press(Button::Right);
+        assert_eq!(shifts(&mut handler, &tetris, 2), [1, 10]);
+    }
+
+    #[test]
+    fn last_pressed_direction_wins_unless_they_cancel() {
+        let tetris = Tetris::with_seed(10, 20, 0);
+        let mut handler = InputHandler::default();
+        handler.press(Button::Left);
+        handler.press(Button::Right);
+        assert_eq!(handler.frame_inputs(&tetris), [Input::ShiftRight]);
+        handler.release(Button::Right);
+        assert_eq!(handler.direction(), Some(Direction::Left));
+        // Voltar para a esquerda recarrega o DAS em vez de deslizar na hora
+        assert_eq!(handler.frame_inputs(&tetris), []);
+
+        handler.set_handling(Handling {
+            priority: DirectionPriority::Cancel,
+            ..Handling::default()
+        });
+        handler.press(Button::Right);
+        assert_eq!(handler.direction(), None);
+        assert_eq!(handler.frame_inputs(&tetris), []);
+    }
+
+    #[test]
+    fn soft_drop_factor_multiplies_gravity() {
+        let tetris = Tetris::with_seed(10, 20, 0);
+        let mut handler = InputHandler::default();
+        handler.press(Button::SoftDrop);
+        // Nível 1 a 20x: 1/3 de célula por frame, mais a célula inicial
+        let drops: usize = (0..32).map(|_| handler.frame_inputs(&tetris).len()).sum();
+        assert_eq!(drops, 11);
+    }
+}
@@ -1,2 +1,9 @@
+pub mod event;
+pub mod font;
+pub mod game;
+pub mod input;
+pub mod palette;
+pub mod replay;
+pub mod snapshot;
 pub mod tetris;
 pub mod ui;
@@ -0,0 +1,19 @@
+use crate::engine::piece::{Cell, PieceKind};
+
+/// Cor em RGB, compartilhada pelos front-ends.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+pub fn cell_color(cell: Cell) -> Rgb {
+    match cell {
+        Cell::Piece(PieceKind::I) => Rgb(0, 212, 255),  // Ciano
+        Cell::Piece(PieceKind::O) => Rgb(255, 215, 0),  // Amarelo
+        Cell::Piece(PieceKind::T) => Rgb(168, 85, 247), // Roxo
+        Cell::Piece(PieceKind::S) => Rgb(16, 185, 129), // Verde
+        Cell::Piece(PieceKind::Z) => Rgb(239, 68, 68),  // Vermelho
+        Cell::Piece(PieceKind::J) => Rgb(59, 130, 246), // Azul
+        Cell::Piece(PieceKind::L) => Rgb(249, 115, 22), // Laranja
+        Cell::Garbage => Rgb(107, 114, 128),            // Lixo (cinza)
+        Cell::Empty => Rgb(15, 52, 96),                 // Vazio (azul escuro)
+    }
+}
@@ -0,0 +1,397 @@
+use crate::engine::board::is_supported_size;
+use crate::interface::game::{Game, Input, FRAME};
+use crate::interface::tetris::Tetris;
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+pub const REPLAY_VERSION: u8 = 1;
+const MAGIC: &[u8; 4] = b"TRPL";
+// Onde os front-ends gravam a última partida
+pub const REPLAY_FILE: &str = "tetris.replay";
+pub const MIN_SPEED: f64 = 0.25;
+pub const MAX_SPEED: f64 = 8.0;
+// Quantos frames o seek dos front-ends pula por tecla
+pub const SEEK_FRAMES: u64 = 5 * 60;
+
+fn input_code(input: Input) -> u8 {
+    Input::ALL.iter().position(|&other| other == input).unwrap() as u8
+}
+
+fn input_from_code(code: u8) -> Option<Input> {
+    Input::ALL.get(code as usize).copied()
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ReplayError {
+    BadMagic,
+    UnsupportedVersion(u8),
+    Truncated,
+    InvalidSize { width: u64, height: u64 },
+    InvalidInput(u8),
+    // O frame de uma entrada passou do que cabe num u64
+    FrameOverflow,
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::BadMagic => write!(f, "not a tetris replay"),
+            ReplayError::UnsupportedVersion(version) => {
+                write!(f, "unsupported replay version {}", version)
+            }
+            ReplayError::Truncated => write!(f, "replay file is truncated"),
+            ReplayError::InvalidSize { width, height } => {
+                write!(f, "unsupported board size {}x{}", width, height)
+            }
+            ReplayError::InvalidInput(code) => write!(f, "invalid input code {}", code),
+            ReplayError::FrameOverflow => write!(f, "input frame is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// Uma partida inteira: tamanho do campo, seed e as entradas com o frame em
+/// que aconteceram. As demais configurações são as padrão de `Tetris`.
+///
+/// No arquivo: `TRPL`, versão, largura, altura, seed (8 bytes little-endian),
+/// total de frames e número de entradas, seguidos de pares (frames desde a
+/// entrada anterior, código da entrada). Inteiros usam LEB128.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Replay {
+    pub width: u32,
+    pub height: u32,
+    pub seed: u64,
+    pub frames: u64,
+    pub inputs: Vec<(u64, Input)>,
+}
+
+impl Replay {
+    pub fn new(width: u32, height: u32, seed: u64) -> Self {
+        Self {
+            width,
+            height,
+            seed,
+            frames: 0,
+            inputs: Vec::new(),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(REPLAY_VERSION);
+        write_varint(&mut bytes, self.width as u64);
+        write_varint(&mut bytes, self.height as u64);
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        write_varint(&mut bytes, self.frames);
+        write_varint(&mut bytes, self.inputs.len() as u64);
+
+        let mut last_frame = 0;
+        for &(frame, input) in &self.inputs {
+            write_varint(&mut bytes, frame - last_frame);
+            bytes.push(input_code(input));
+            last_frame = frame;
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ReplayError> {
+        let mut reader = Reader { bytes, pos: 0 };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(ReplayError::BadMagic);
+        }
+        let version = reader.byte()?;
+        if version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+
+        let (width, height) = (reader.varint()?, reader.varint()?);
+        if !is_supported_size(width, height) {
+            return Err(ReplayError::InvalidSize { width, height });
+        }
+        let seed = u64::from_le_bytes(reader.take(8)?.try_into().unwrap());
+        let frames = reader.varint()?;
+        let count = reader.varint()?;
+
+        let mut inputs = Vec::new();
+        let mut frame = 0;
+        for _ in 0..count {
+            frame = reader
+                .varint()?
+                .checked_add(frame)
+                .ok_or(ReplayError::FrameOverflow)?;
+            let code = reader.byte()?;
+            let input = input_from_code(code).ok_or(ReplayError::InvalidInput(code))?;
+            inputs.push((frame, input));
+        }
+
+        Ok(Self {
+            width: width as u32,
+            height: height as u32,
+            seed,
+            frames,
+            inputs,
+        })
+    }
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ReplayError> {
+        let slice = self
+            .bytes
+            .get(self.pos..self.pos + len)
+            .ok_or(ReplayError::Truncated)?;
+        self.pos += len;
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> Result<u8, ReplayError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64, ReplayError> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7F) as u64) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        Ok(value)
+    }
+}
+
+/// Grava as entradas de uma partida iniciada com `Tetris::with_seed`.
+#[derive(Debug)]
+pub struct Recorder {
+    replay: Replay,
+}
+
+impl Recorder {
+    pub fn new(tetris: &Tetris) -> Self {
+        Self {
+            replay: Replay::new(tetris.width() as u32, tetris.height() as u32, tetris.seed()),
+        }
+    }
+
+    // Partida nova já gravando. Só partidas novas são gravadas: o replay
+    // começa do seed, então uma partida continuada não teria como ser refeita
+    pub fn new_game(width: u32, height: u32) -> (Game, Self) {
+        let tetris = Tetris::new(width, height);
+        let recorder = Self::new(&tetris);
+        (Game::new(tetris), recorder)
+    }
+
+    // As mesmas entradas passadas a `Game::step` neste frame
+    pub fn record_frame(&mut self, inputs: &[Input]) {
+        let frame = self.replay.frames;
+        self.replay
+            .inputs
+            .extend(inputs.iter().map(|&input| (frame, input)));
+        self.replay.frames += 1;
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+}
+
+/// Reproduz um `Replay` frame a frame, com pausa, velocidade e busca.
+#[derive(Debug)]
+pub struct Player {
+    replay: Replay,
+    game: Game,
+    // Posição no replay; o relógio do `Game` volta a zero a cada reinício
+    frame: u64,
+    cursor: usize,
+    paused: bool,
+    speed: f64,
+    pending_frames: f64,
+}
+
+impl Player {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            game: Game::with_seed(replay.width, replay.height, replay.seed),
+            replay,
+            frame: 0,
+            cursor: 0,
+            paused: false,
+            speed: 1.0,
+            pending_frames: 0.0,
+        }
+    }
+
+    pub fn tetris(&self) -> &Tetris {
+        self.game.tetris()
+    }
+
+    pub fn tetris_mut(&mut self) -> &mut Tetris {
+        self.game.tetris_mut()
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn total_frames(&self) -> u64 {
+        self.replay.frames
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frame() >= self.replay.frames
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: f64) {
+        self.speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+    }
+
+    pub fn step(&mut self) {
+        if self.is_finished() {
+            return;
+        }
+
+        let start = self.cursor;
+        while let Some(&(frame, _)) = self.replay.inputs.get(self.cursor) {
+            if frame > self.frame() {
+                break;
+            }
+            self.cursor += 1;
+        }
+        let inputs: Vec<Input> = self.replay.inputs[start..self.cursor]
+            .iter()
+            .map(|&(_, input)| input)
+            .collect();
+        self.game.step(&inputs);
+        self.frame += 1;
+    }
+
+    // Voltar no tempo recomeça do seed e simula até o frame pedido
+    pub fn seek(&mut self, frame: u64) {
+        let frame = frame.min(self.replay.frames);
+        if frame < self.frame() {
+            let (paused, speed) = (self.paused, self.speed);
+            *self = Self::new(self.replay.clone());
+            self.paused = paused;
+            self.speed = speed;
+        }
+        while self.frame() < frame {
+            self.step();
+        }
+        self.tetris_mut().drain_events().for_each(drop);
+        self.pending_frames = 0.0;
+    }
+
+    // Avança conforme o tempo real passado e a velocidade escolhida
+    pub fn update(&mut self, elapsed: Duration) {
+        if self.paused {
+            return;
+        }
+
+        self.pending_frames += elapsed.as_secs_f64() / FRAME.as_secs_f64() * self.speed;
+        while self.pending_frames >= 1.0 && !self.is_finished() {
+            self.step();
+            self.pending_frames -= 1.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_varint, Player, Recorder, Replay, ReplayError, MAGIC};
+    use crate::engine::board::MAX_HEIGHT;
+    use crate::interface::game::{Game, Input};
+    use crate::interface::tetris::Tetris;
+
+    fn record_game() -> (Replay, Tetris) {
+        let mut game = Game::with_seed(10, 20, 11);
+        let mut recorder = Recorder::new(game.tetris());
+        for frame in 0..3000u64 {
+            let inputs = match frame {
+                // Reinício no meio: a posição no replay não volta a zero
+                2000 => vec![Input::Reset],
+                _ if frame % 7 == 0 => vec![Input::ALL[(frame / 7 % 8) as usize]],
+                _ => vec![],
+            };
+            game.step(&inputs);
+            recorder.record_frame(&inputs);
+        }
+        (recorder.replay().clone(), game.into_tetris())
+    }
+
+    #[test]
+    fn playback_reproduces_the_recorded_game() {
+        let (replay, tetris) = record_game();
+        let mut player = Player::new(Replay::from_bytes(&replay.to_bytes()).unwrap());
+        player.seek(replay.frames);
+        assert!(player.is_finished());
+        assert_eq!(player.tetris().snapshot(), tetris.snapshot());
+
+        // Voltar e avançar de novo chega ao mesmo estado
+        player.seek(1000);
+        assert_eq!(player.frame(), 1000);
+        player.seek(replay.frames);
+        assert_eq!(player.tetris().snapshot(), tetris.snapshot());
+    }
+
+    #[test]
+    fn damaged_files_are_rejected() {
+        let (replay, _) = record_game();
+        let bytes = replay.to_bytes();
+        assert_eq!(Replay::from_bytes(&bytes[..bytes.len() - 1]), Err(ReplayError::Truncated));
+        assert_eq!(Replay::from_bytes(b"nope"), Err(ReplayError::BadMagic));
+
+        let resized = |width: u32, height: u32| {
+            let replay = Replay {
+                width,
+                height,
+                ..replay.clone()
+            };
+            Replay::from_bytes(&replay.to_bytes())
+        };
+        assert_eq!(resized(100, 20), Err(ReplayError::InvalidSize { width: 100, height: 20 }));
+        assert_eq!(resized(3, 20), Err(ReplayError::InvalidSize { width: 3, height: 20 }));
+        assert_eq!(resized(10, 0), Err(ReplayError::InvalidSize { width: 10, height: 0 }));
+        assert_eq!(resized(10, 5000), Err(ReplayError::InvalidSize { width: 10, height: 5000 }));
+        assert!(resized(4, MAX_HEIGHT).is_ok());
+
+        // Duas entradas cujos intervalos somados estouram o u64
+        let mut bytes = bytes[..MAGIC.len() + 1].to_vec();
+        write_varint(&mut bytes, 10);
+        write_varint(&mut bytes, 20);
+        bytes.extend(0u64.to_le_bytes());
+        write_varint(&mut bytes, 100);
+        write_varint(&mut bytes, 2);
+        for _ in 0..2 {
+            write_varint(&mut bytes, u64::MAX);
+            bytes.push(0);
+        }
+        assert_eq!(Replay::from_bytes(&bytes), Err(ReplayError::FrameOverflow));
+    }
+}
@@ -0,0 +1,285 @@
+use crate::engine::board::{is_supported_size, Board};
+use crate::engine::piece::{Cell, PieceKind};
+use crate::engine::position::Pos;
+use crate::engine::rotation::Rotation;
+use crate::engine::shape::Shape;
+use crate::interface::tetris::{GameOverReason, Movement};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+pub const SNAPSHOT_VERSION: u32 = 1;
+const HEADER: &str = "tetris-snapshot";
+// Onde os front-ends guardam a partida ao sair com "save and quit"
+pub const SAVE_FILE: &str = "tetris.save";
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SnapshotError {
+    MissingHeader,
+    UnsupportedVersion(u32),
+    MissingField(String),
+    InvalidField { key: String, value: String },
+    // A partida salva já tinha terminado
+    GameOver,
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::MissingHeader => write!(f, "not a tetris snapshot"),
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "unsupported snapshot version {}", version)
+            }
+            SnapshotError::MissingField(key) => write!(f, "missing field {:?}", key),
+            SnapshotError::InvalidField { key, value } => {
+                write!(f, "invalid value for {:?}: {:?}", key, value)
+            }
+            SnapshotError::GameOver => write!(f, "the game had already ended"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+/// Estado salvo de uma partida, criado por `Tetris::snapshot`. Em texto: uma
+/// linha de cabeçalho com a versão seguida de linhas `chave valor`, de modo
+/// que versões futuras possam acrescentar campos sem quebrar saves antigos.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Snapshot {
+    fields: Vec<(String, String)>,
+}
+
+impl Snapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, key: &str, value: impl Display) {
+        let value = value.to_string();
+        match self.fields.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value,
+            None => self.fields.push((key.to_string(), value)),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Result<&str, SnapshotError> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+            .ok_or_else(|| SnapshotError::MissingField(key.to_string()))
+    }
+
+    pub fn parse<T: FromStr>(&self, key: &str) -> Result<T, SnapshotError> {
+        self.get(key)?.parse().map_err(|_| self.invalid(key))
+    }
+
+    // Campo com valores separados por espaço
+    pub fn parse_list<T: FromStr>(&self, key: &str) -> Result<Vec<T>, SnapshotError> {
+        self.get(key)?
+            .split_whitespace()
+            .map(|value| value.parse().map_err(|_| self.invalid(key)))
+            .collect()
+    }
+
+    pub fn invalid(&self, key: &str) -> SnapshotError {
+        SnapshotError::InvalidField {
+            key: key.to_string(),
+            value: self.get(key).unwrap_or_default().to_string(),
+        }
+    }
+}
+
+impl Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {}", HEADER, SNAPSHOT_VERSION)?;
+        for (key, value) in &self.fields {
+            writeln!(f, "{} {}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Snapshot {
+    type Err = SnapshotError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let version = lines
+            .next()
+            .and_then(|line| line.trim().strip_prefix(HEADER))
+            .and_then(|version| version.trim().parse().ok())
+            .ok_or(SnapshotError::MissingHeader)?;
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+
+        let mut snapshot = Snapshot::new();
+        for line in lines.map(str::trim).filter(|line| !line.is_empty()) {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            snapshot.set(key, value);
+        }
+        Ok(snapshot)
+    }
+}
+
+fn cell_char(cell: Cell) -> char {
+    match cell {
+        Cell::Empty => '.',
+        Cell::Garbage => 'G',
+        Cell::Piece(kind) => kind.letter(),
+    }
+}
+
+fn parse_cell(c: char) -> Option<Cell> {
+    match c {
+        '.' => Some(Cell::Empty),
+        'G' => Some(Cell::Garbage),
+        _ => parse_kind(c).map(Cell::Piece),
+    }
+}
+
+pub(crate) fn parse_kind(c: char) -> Option<PieceKind> {
+    c.to_string().parse().ok()
+}
+
+// Linhas de cima para baixo, incluindo a zona oculta, separadas por '/'
+pub(crate) fn format_board(board: &Board) -> String {
+    (board.top()..board.height())
+        .map(|y| {
+            (0..board.width())
+                .map(|x| cell_char(board.get(Pos(x, y))))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+pub(crate) fn parse_board(width: u32, height: u32, buffer: u32, rows: &str) -> Option<Board> {
+    let rows: Vec<&str> = rows.split('/').collect();
+    let total = height.checked_add(buffer)?;
+    if !is_supported_size(width as u64, height as u64) || rows.len() != total as usize {
+        return None;
+    }
+    let mut board = Board::with_buffer(width, height, buffer);
+    for (row, y) in rows.into_iter().zip(board.top()..) {
+        if row.chars().count() != width as usize {
+            return None;
+        }
+        for (c, x) in row.chars().zip(0..) {
+            board.set(Pos(x, y), parse_cell(c)?);
+        }
+    }
+    Some(board)
+}
+
+fn rotation_name(rotation: Rotation) -> &'static str {
+    match rotation {
+        Rotation::Spawn => "0",
+        Rotation::Right => "R",
+        Rotation::Half => "2",
+        Rotation::Left => "L",
+    }
+}
+
+// "T R 3 5": tipo, rotação SRS e deslocamento desde a caixa de nascimento
+pub(crate) fn format_piece(shape: &Shape) -> String {
+    let Pos(x, y) = shape.offset();
+    format!("{} {} {} {}", shape.kind(), rotation_name(shape.rotation()), x, y)
+}
+
+pub(crate) fn parse_piece(piece: &str) -> Option<Shape> {
+    let mut parts = piece.split_whitespace();
+    let kind = parts.next()?.parse().ok()?;
+    let rotation = match parts.next()? {
+        "0" => Rotation::Spawn,
+        "R" => Rotation::Right,
+        "2" => Rotation::Half,
+        "L" => Rotation::Left,
+        _ => return None,
+    };
+    let x = parts.next()?.parse().ok()?;
+    let y = parts.next()?.parse().ok()?;
+    Some(Shape::placed(kind, rotation, Pos(x, y)))
+}
+
+pub(crate) fn format_movement(movement: Option<Movement>) -> String {
+    match movement {
+        None => "none".to_string(),
+        Some(Movement::Shift) => "shift".to_string(),
+        Some(Movement::Fall) => "fall".to_string(),
+        Some(Movement::Rotate { kick, half_turn }) => {
+            let prefix = if half_turn { "rotate180" } else { "rotate" };
+            format!("{}:{}", prefix, kick)
+        }
+    }
+}
+
+pub(crate) fn parse_movement(movement: &str) -> Option<Option<Movement>> {
+    match movement {
+        "none" => Some(None),
+        "shift" => Some(Some(Movement::Shift)),
+        "fall" => Some(Some(Movement::Fall)),
+        _ => {
+            let (half_turn, kick) = match movement.strip_prefix("rotate180:") {
+                Some(kick) => (true, kick),
+                None => (false, movement.strip_prefix("rotate:")?),
+            };
+            let kick = kick.parse().ok()?;
+            Some(Some(Movement::Rotate { kick, half_turn }))
+        }
+    }
+}
+
+pub(crate) fn format_game_over(reason: Option<GameOverReason>) -> &'static str {
+    match reason {
+        None => "-",
+        Some(GameOverReason::BlockOut) => "block-out",
+        Some(GameOverReason::LockOut) => "lock-out",
+        Some(GameOverReason::TopOut) => "top-out",
+    }
+}
+
+pub(crate) fn parse_game_over(reason: &str) -> Option<Option<GameOverReason>> {
+    match reason {
+        "-" => Some(None),
+        "block-out" => Some(Some(GameOverReason::BlockOut)),
+        "lock-out" => Some(Some(GameOverReason::LockOut)),
+        "top-out" => Some(Some(GameOverReason::TopOut)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_movement, parse_movement, Snapshot, SnapshotError};
+    use crate::interface::tetris::Movement;
+
+    #[test]
+    fn text_round_trips_and_checks_the_version() {
+        let mut snapshot = Snapshot::new();
+        snapshot.set("score", 1200);
+        snapshot.set("queue", "TJL");
+        snapshot.set("hold", "");
+        let parsed: Snapshot = snapshot.to_string().parse().unwrap();
+        assert_eq!(parsed, snapshot);
+        assert_eq!(parsed.parse::<u32>("score"), Ok(1200));
+        assert_eq!(parsed.get("hold"), Ok(""));
+        assert!(parsed.parse::<u32>("queue").is_err());
+
+        assert_eq!(
+            "tetris-snapshot 99\n".parse::<Snapshot>(),
+            Err(SnapshotError::UnsupportedVersion(99))
+        );
+        assert_eq!("score 1".parse::<Snapshot>(), Err(SnapshotError::MissingHeader));
+    }
+
+    #[test]
+    fn rotations_keep_their_kick_table() {
+        for half_turn in [false, true] {
+            let movement = Some(Movement::Rotate { kick: 4, half_turn });
+            assert_eq!(parse_movement(&format_movement(movement)), Some(movement));
+        }
+        assert_eq!(format_movement(Some(Movement::Rotate { kick: 2, half_turn: false })), "rotate:2");
+        assert_eq!(parse_movement("rotate:x"), None);
+    }
+}
@@ -1,6 +1,30 @@
+use crate::engine::board::{is_supported_size, Board, MAX_HEIGHT, MAX_WIDTH};
+use crate::engine::piece::{Cell, PieceKind};
 use crate::engine::position::Pos;
+use crate::engine::randomizer::{self, Bag, Randomizer};
+use crate::engine::rng::GameRng;
+use crate::engine::rotation::{self, Rotation};
+use crate::engine::scoring::{
+    self, Clear, ClearKind, Guideline, ScoreBreakdown, ScoringRule, Spin,
+};
 use crate::engine::shape::Shape;
-use std::{collections::HashSet, mem};
+use crate::interface::event::GameEvent;
+use crate::interface::snapshot::{self, Snapshot, SnapshotError};
+use rand::RngCore;
+use std::collections::VecDeque;
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+pub const MAX_PREVIEW: usize = 7;
+pub const DEFAULT_LOCK_DELAY: Duration = Duration::from_millis(500);
+pub const DEFAULT_MAX_LOCK_RESETS: u32 = 15;
+pub const LINES_PER_LEVEL: u32 = 10;
+// Linhas ocultas acima do campo visível, como no guideline (40 no total)
+pub const DEFAULT_BUFFER_ROWS: u32 = 20;
+// 20G: a peça cai a altura inteira do campo em um único frame
+pub const MAX_GRAVITY: f64 = 20.0;
+// Primeiro nível em que a curva do guideline passa de 20G
+pub const MAX_GRAVITY_LEVEL: u32 = 19;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Direction {
@@ -8,43 +32,380 @@ pub enum Direction {
     Right,
 }
 
+// Último movimento bem-sucedido da peça atual, usado para detectar T-spins
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Movement {
+    Shift,
+    Fall,
+    // `half_turn` nas rotações de 180°, que usam outra tabela de kicks
+    Rotate { kick: usize, half_turn: bool },
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum GameOverReason {
+    // A peça nova nasceu sobre blocos já travados
+    BlockOut,
+    // A peça travou inteira acima do campo visível
+    LockOut,
+    // A pilha chegou ao topo da zona oculta
+    TopOut,
+}
+
+impl Display for GameOverReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            GameOverReason::BlockOut => "Block Out",
+            GameOverReason::LockOut => "Lock Out",
+            GameOverReason::TopOut => "Top Out",
+        };
+        f.write_str(reason)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Statistics {
+    pub pieces: u32,
+    pub tetrises: u32,
+    pub t_spins: u32,
+    pub perfect_clears: u32,
+    pub max_combo: u32,
+}
+
 #[derive(Debug)]
 pub struct Tetris {
     width: i32,
     height: i32,
     curr_shape: Shape,
-    fixed_shapes: Vec<Shape>,
-    game_over: bool,
+    queue: VecDeque<Shape>,
+    preview_len: usize,
+    held_shape: Option<Shape>,
+    hold_used: bool,
+    lock_delay: Duration,
+    max_lock_resets: u32,
+    lock_timer: Duration,
+    lock_resets: u32,
+    lowest_row: i32,
+    last_move: Option<Movement>,
+    board: Board,
+    game_over: Option<GameOverReason>,
     score: u32,
+    lines: u32,
+    scoring: Box<dyn ScoringRule>,
+    combo: Option<u32>,
+    back_to_back: bool,
+    last_clear: Option<ScoreBreakdown>,
+    stats: Statistics,
+    randomizer: Box<dyn Randomizer>,
+    seed: u64,
+    rng: GameRng,
+    events: Vec<GameEvent>,
 }
 
 impl Tetris {
+    /// Partida com seed aleatório. O campo tem no máximo `MAX_WIDTH` (64)
+    /// colunas, uma linha do bitboard; larguras maiores causam pânico.
     pub fn new(width: u32, height: u32) -> Self {
-        Self {
+        Self::with_seed(width, height, rand::random())
+    }
+
+    /// Como `new`, com o mesmo limite de `MAX_WIDTH` colunas.
+    pub fn with_seed(width: u32, height: u32, seed: u64) -> Self {
+        Self::with_randomizer(width, height, seed, Box::new(Bag::seven()))
+    }
+
+    /// Com outro gerador de peças; também limitado a `MAX_WIDTH` colunas.
+    pub fn with_randomizer(
+        width: u32,
+        height: u32,
+        seed: u64,
+        mut randomizer: Box<dyn Randomizer>,
+    ) -> Self {
+        assert!(
+            width <= MAX_WIDTH,
+            "a Tetris board is at most {} columns wide, got {}",
+            MAX_WIDTH,
+            width
+        );
+        let mut rng = GameRng::new(seed);
+        let first_shape = Shape::new(randomizer.next_piece(&mut rng));
+        let mut tetris = Self {
             width: width as i32,
             height: height as i32,
-            curr_shape: &Shape::new_rand() + Pos((width as i32) / 2, 0),
-            fixed_shapes: vec![],
-            game_over: false,
+            curr_shape: first_shape.clone(),
+            queue: VecDeque::new(),
+            preview_len: 5,
+            held_shape: None,
+            hold_used: false,
+            lock_delay: DEFAULT_LOCK_DELAY,
+            max_lock_resets: DEFAULT_MAX_LOCK_RESETS,
+            lock_timer: Duration::ZERO,
+            lock_resets: 0,
+            lowest_row: 0,
+            last_move: None,
+            board: Board::with_buffer(width, height, DEFAULT_BUFFER_ROWS),
+            game_over: None,
             score: 0,
-        }
+            lines: 0,
+            scoring: Box::new(Guideline),
+            combo: None,
+            back_to_back: false,
+            last_clear: None,
+            stats: Statistics::default(),
+            randomizer,
+            seed,
+            rng,
+            events: Vec::new(),
+        };
+        tetris.fill_queue();
+        tetris.spawn(first_shape);
+        tetris
     }
 
     pub fn reset(&mut self) {
-        self.curr_shape = &Shape::new_rand() + Pos(self.width / 2, 0);
-        self.fixed_shapes.clear();
-        self.game_over = false;
+        // O novo seed sai do gerador atual, então reinícios também são reproduzíveis
+        self.seed = self.rng.next_u64();
+        self.rng = GameRng::new(self.seed);
+        self.randomizer.reset();
+        self.queue.clear();
+        self.held_shape = None;
+        self.hold_used = false;
+        self.board.clear();
+        self.game_over = None;
         self.score = 0;
+        self.lines = 0;
+        self.combo = None;
+        self.back_to_back = false;
+        self.last_clear = None;
+        self.stats = Statistics::default();
+        self.events.push(GameEvent::Reset);
+        let first_shape = self.next_shape();
+        self.spawn(first_shape);
+    }
+
+    /// Tudo que é preciso para continuar a partida depois com `restore`.
+    /// Eventos pendentes e o último `ScoreBreakdown` não são salvos.
+    pub fn snapshot(&self) -> Snapshot {
+        let mut snapshot = Snapshot::new();
+        snapshot.set(
+            "size",
+            format!("{} {} {}", self.width, self.height, self.board.buffer()),
+        );
+        snapshot.set("seed", self.seed);
+        snapshot.set("rng", self.rng.state());
+        snapshot.set("randomizer", self.randomizer.save());
+        snapshot.set("scoring", self.scoring.name());
+        snapshot.set("board", snapshot::format_board(&self.board));
+        snapshot.set("piece", snapshot::format_piece(&self.curr_shape));
+        let queue: String = self.queue.iter().map(|shape| shape.kind().letter()).collect();
+        snapshot.set("queue", queue);
+        snapshot.set("preview", self.preview_len);
+        let hold = self.held_shape.as_ref().map(|shape| shape.kind().to_string());
+        snapshot.set("hold", hold.unwrap_or_default());
+        snapshot.set("hold_used", self.hold_used);
+        snapshot.set("score", self.score);
+        snapshot.set("lines", self.lines);
+        snapshot.set("combo", self.combo.map_or("-".to_string(), |combo| combo.to_string()));
+        snapshot.set("back_to_back", self.back_to_back);
+        let stats = &self.stats;
+        snapshot.set(
+            "stats",
+            format!(
+                "{} {} {} {} {}",
+                stats.pieces, stats.tetrises, stats.t_spins, stats.perfect_clears, stats.max_combo
+            ),
+        );
+        snapshot.set("lock_delay", self.lock_delay.as_micros());
+        snapshot.set("max_lock_resets", self.max_lock_resets);
+        snapshot.set("lock_timer", self.lock_timer.as_micros());
+        snapshot.set("lock_resets", self.lock_resets);
+        snapshot.set("lowest_row", self.lowest_row);
+        snapshot.set("last_move", snapshot::format_movement(self.last_move));
+        snapshot.set("game_over", snapshot::format_game_over(self.game_over));
+        snapshot
+    }
+
+    pub fn restore(snapshot: &Snapshot) -> Result<Self, SnapshotError> {
+        let invalid = |key: &str| snapshot.invalid(key);
+
+        let size: Vec<u32> = snapshot.parse_list("size")?;
+        let &[width, height, buffer] = size.as_slice() else {
+            return Err(invalid("size"));
+        };
+        // Mesmos limites dos replays; um save adulterado não pode pedir um
+        // campo gigante
+        if !is_supported_size(width as u64, height as u64) || buffer > MAX_HEIGHT {
+            return Err(invalid("size"));
+        }
+        let board = snapshot::parse_board(width, height, buffer, snapshot.get("board")?)
+            .ok_or_else(|| invalid("board"))?;
+        let queue = snapshot
+            .get("queue")?
+            .chars()
+            .map(|c| snapshot::parse_kind(c).map(Shape::new))
+            .collect::<Option<_>>()
+            .ok_or_else(|| invalid("queue"))?;
+        let held_shape = match snapshot.get("hold")? {
+            "" => None,
+            hold => Some(Shape::new(hold.parse().map_err(|_| invalid("hold"))?)),
+        };
+        let combo = match snapshot.get("combo")? {
+            "-" => None,
+            _ => Some(snapshot.parse("combo")?),
+        };
+        let stats: Vec<u32> = snapshot.parse_list("stats")?;
+        let &[pieces, tetrises, t_spins, perfect_clears, max_combo] = stats.as_slice() else {
+            return Err(invalid("stats"));
+        };
+
+        Ok(Self {
+            width: width as i32,
+            height: height as i32,
+            curr_shape: snapshot::parse_piece(snapshot.get("piece")?)
+                .ok_or_else(|| invalid("piece"))?,
+            queue,
+            preview_len: snapshot.parse::<usize>("preview")?.min(MAX_PREVIEW),
+            held_shape,
+            hold_used: snapshot.parse("hold_used")?,
+            lock_delay: Duration::from_micros(snapshot.parse("lock_delay")?),
+            max_lock_resets: snapshot.parse("max_lock_resets")?,
+            lock_timer: Duration::from_micros(snapshot.parse("lock_timer")?),
+            lock_resets: snapshot.parse("lock_resets")?,
+            lowest_row: snapshot.parse("lowest_row")?,
+            last_move: snapshot::parse_movement(snapshot.get("last_move")?)
+                .ok_or_else(|| invalid("last_move"))?,
+            board,
+            game_over: snapshot::parse_game_over(snapshot.get("game_over")?)
+                .ok_or_else(|| invalid("game_over"))?,
+            score: snapshot.parse("score")?,
+            lines: snapshot.parse("lines")?,
+            scoring: scoring::rule_by_name(snapshot.get("scoring")?)
+                .ok_or_else(|| invalid("scoring"))?,
+            combo,
+            back_to_back: snapshot.parse("back_to_back")?,
+            last_clear: None,
+            stats: Statistics {
+                pieces,
+                tetrises,
+                t_spins,
+                perfect_clears,
+                max_combo,
+            },
+            randomizer: randomizer::load(snapshot.get("randomizer")?)
+                .ok_or_else(|| invalid("randomizer"))?,
+            seed: snapshot.parse("seed")?,
+            rng: GameRng::new(snapshot.parse("rng")?),
+            events: Vec::new(),
+        })
     }
 
     pub fn is_game_over(&self) -> bool {
+        self.game_over.is_some()
+    }
+
+    pub fn game_over_reason(&self) -> Option<GameOverReason> {
         self.game_over
     }
 
+    // Eventos acumulados desde a última chamada; a fila cresce até ser esvaziada
+    pub fn drain_events(&mut self) -> impl Iterator<Item = GameEvent> + '_ {
+        self.events.drain(..)
+    }
+
+    fn end_game(&mut self, reason: GameOverReason) {
+        self.game_over = Some(reason);
+        self.events.push(GameEvent::GameOver { reason });
+    }
+
     pub fn score(&self) -> u32 {
         self.score
     }
 
+    pub fn set_scoring_rule(&mut self, scoring: Box<dyn ScoringRule>) {
+        self.scoring = scoring;
+    }
+
+    // Detalhes da última limpeza, para exibir "Back-to-Back Tetris +1200"
+    pub fn last_clear(&self) -> Option<&ScoreBreakdown> {
+        self.last_clear.as_ref()
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn statistics(&self) -> &Statistics {
+        &self.stats
+    }
+
+    pub fn lines_cleared(&self) -> u32 {
+        self.lines
+    }
+
+    pub fn level(&self) -> u32 {
+        1 + self.lines / LINES_PER_LEVEL
+    }
+
+    // Células por frame (a 60 fps), pela curva do guideline
+    pub fn gravity(&self) -> f64 {
+        if self.level() >= MAX_GRAVITY_LEVEL {
+            return MAX_GRAVITY;
+        }
+        let level = self.level() as i32 - 1;
+        let seconds_per_row = (0.8 - level as f64 * 0.007).powi(level);
+        (1.0 / (seconds_per_row * 60.0)).min(MAX_GRAVITY)
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn preview_len(&self) -> usize {
+        self.preview_len
+    }
+
+    pub fn set_preview_len(&mut self, len: usize) {
+        self.preview_len = len.min(MAX_PREVIEW);
+        self.fill_queue();
+    }
+
+    // Peças já sorteadas que ultrapassam a prévia ficam guardadas, então a
+    // sequência não depende do tamanho configurado
+    pub fn next_pieces(&self) -> impl Iterator<Item = &Shape> {
+        self.queue.iter().take(self.preview_len)
+    }
+
+    pub fn lock_delay(&self) -> Duration {
+        self.lock_delay
+    }
+
+    pub fn set_lock_delay(&mut self, lock_delay: Duration) {
+        self.lock_delay = lock_delay;
+    }
+
+    pub fn max_lock_resets(&self) -> u32 {
+        self.max_lock_resets
+    }
+
+    pub fn set_max_lock_resets(&mut self, max_lock_resets: u32) {
+        self.max_lock_resets = max_lock_resets;
+    }
+
+    pub fn last_move(&self) -> Option<Movement> {
+        self.last_move
+    }
+
+    pub fn current_piece(&self) -> &Shape {
+        &self.curr_shape
+    }
+
+    pub fn held_piece(&self) -> Option<&Shape> {
+        self.held_shape.as_ref()
+    }
+
+    pub fn can_hold(&self) -> bool {
+        !self.hold_used && !self.is_game_over()
+    }
+
     pub fn width(&self) -> i32 {
         self.width
     }
@@ -53,16 +414,27 @@ impl Tetris {
         self.height
     }
 
+    pub fn buffer_rows(&self) -> u32 {
+        self.board.buffer() as u32
+    }
+
+    // Recria o campo vazio; feito para ser chamado antes de a partida começar
+    pub fn set_buffer_rows(&mut self, rows: u32) {
+        self.board = Board::with_buffer(self.width as u32, self.height as u32, rows);
+        self.game_over = None;
+        self.spawn(Shape::new(self.curr_shape.kind()));
+    }
+
+    // A zona oculta acima do campo é espaço válido para as peças
     pub fn is_out_of_bounds(&self, shape: &Shape) -> bool {
+        let top = self.board.top();
         !shape
             .iter_positions()
-            .all(|pos| 0 <= pos.0 && pos.0 < self.width && 0 <= pos.1 && pos.1 < self.height)
+            .all(|pos| 0 <= pos.0 && pos.0 < self.width && top <= pos.1 && pos.1 < self.height)
     }
 
     pub fn is_colliding(&self, shape: &Shape) -> bool {
-        self.fixed_shapes
-            .iter()
-            .any(|fixed_shape| fixed_shape.collides_with(shape))
+        self.board.collides(shape)
     }
 
     pub fn iter_position(&self) -> impl Iterator<Item = Pos> {
@@ -71,19 +443,32 @@ impl Tetris {
         (0..height).flat_map(move |y| (0..width).map(move |x| Pos(x, y)))
     }
 
-    pub fn get(&self, pos: Pos) -> Option<&'static str> {
+    pub fn get(&self, pos: Pos) -> Cell {
         if self.curr_shape.has_position(pos) {
-            Some(self.curr_shape.typ())
+            Cell::Piece(self.curr_shape.kind())
         } else {
-            self.fixed_shapes
-                .iter()
-                .find(|shape| shape.has_position(pos))
-                .map(|shape| shape.typ())
+            self.board.get(pos)
+        }
+    }
+
+    // Avança o relógio do jogo: a peça trava depois de `lock_delay` apoiada
+    pub fn update(&mut self, elapsed: Duration) {
+        if self.is_game_over() {
+            return;
+        }
+
+        if self.is_grounded() {
+            self.lock_timer += elapsed;
+            if self.lock_timer >= self.lock_delay {
+                self.lock_piece();
+            }
+        } else {
+            self.lock_timer = Duration::ZERO;
         }
     }
 
     pub fn tick(&mut self) {
-        if self.game_over {
+        if self.is_game_over() {
             return;
         }
 
@@ -92,24 +477,135 @@ impl Tetris {
         if self.is_out_of_bounds(&translated_curr_shape)
             || self.is_colliding(&translated_curr_shape)
         {
-            let new_fixed_shape = mem::replace(
-                &mut self.curr_shape,
-                &Shape::new_rand() + Pos(self.width / 2, 0),
-            );
-
-            self.fixed_shapes.push(new_fixed_shape);
-            self.remove_full_lines();
-
-            if self.is_colliding(&self.curr_shape) {
-                self.game_over = true;
+            // Com lock delay zerado a peça trava na hora; senão quem trava é `update`
+            if self.lock_timer >= self.lock_delay {
+                self.lock_piece();
             }
         } else {
             self.curr_shape = translated_curr_shape;
+            self.last_move = Some(Movement::Fall);
+            self.track_lowest_row();
+        }
+    }
+
+    pub fn soft_drop(&mut self) {
+        if self.is_game_over() {
+            return;
+        }
+
+        if !self.is_grounded() {
+            self.score += self.scoring.soft_drop(1);
+            self.events.push(GameEvent::SoftDropped);
+        }
+        self.tick();
+    }
+
+    pub fn is_grounded(&self) -> bool {
+        let translated_curr_shape = &self.curr_shape + Pos(0, 1);
+        self.is_out_of_bounds(&translated_curr_shape) || self.is_colliding(&translated_curr_shape)
+    }
+
+    fn lock_piece(&mut self) {
+        let spin = self.detect_spin();
+        let locked_above = self.curr_shape.iter_positions().all(|pos| pos.1 < 0);
+
+        self.board.place(&self.curr_shape);
+        self.stats.pieces += 1;
+        self.hold_used = false;
+        self.events.push(GameEvent::Locked {
+            kind: self.curr_shape.kind(),
+            spin,
+        });
+        let rows = self.remove_full_lines();
+        self.score_lines(rows, spin);
+
+        if locked_above {
+            self.end_game(GameOverReason::LockOut);
+        } else if self.board.buffer() > 0 && self.board.row(self.board.top()) != 0 {
+            self.end_game(GameOverReason::TopOut);
+        } else {
+            let next_shape = self.next_shape();
+            self.spawn(next_shape);
+        }
+    }
+
+    fn reset_lock_state(&mut self) {
+        self.lock_timer = Duration::ZERO;
+        self.lock_resets = 0;
+        self.lowest_row = self.bottom_row();
+        self.last_move = None;
+    }
+
+    // Regra dos 3 cantos: o T só conta se o último movimento foi uma rotação
+    fn detect_spin(&self) -> Spin {
+        let Some(Movement::Rotate { kick, half_turn }) = self.last_move else {
+            return Spin::None;
+        };
+        if self.curr_shape.kind() != PieceKind::T {
+            return Spin::None;
+        }
+
+        let center = self.curr_shape.center();
+        let is_blocked = |offset: Pos| {
+            let pos = center + offset;
+            pos.0 < 0
+                || pos.0 >= self.width
+                || pos.1 < self.board.top()
+                || pos.1 >= self.height
+                || self.board.is_occupied(pos)
+        };
+        let corners = [Pos(-1, -1), Pos(1, -1), Pos(1, 1), Pos(-1, 1)];
+        if corners.iter().filter(|&&corner| is_blocked(corner)).count() < 3 {
+            return Spin::None;
+        }
+
+        // Cantos do lado para onde o T aponta
+        let front = match self.curr_shape.rotation() {
+            Rotation::Spawn => [Pos(-1, -1), Pos(1, -1)],
+            Rotation::Right => [Pos(1, -1), Pos(1, 1)],
+            Rotation::Half => [Pos(1, 1), Pos(-1, 1)],
+            Rotation::Left => [Pos(-1, 1), Pos(-1, -1)],
+        };
+        // O último kick das rotações de 90° (o "TST kick") sempre conta como
+        // T-spin completo; na tabela de 180° o índice 4 é um deslocamento comum
+        let tst_kick = kick == 4 && !half_turn;
+        if front.into_iter().all(is_blocked) || tst_kick {
+            Spin::Full
+        } else {
+            Spin::Mini
+        }
+    }
+
+    fn bottom_row(&self) -> i32 {
+        self.curr_shape.iter_positions().map(|pos| pos.1).max().unwrap_or(0)
+    }
+
+    // Descer a uma linha inédita devolve todos os resets de lock delay
+    fn track_lowest_row(&mut self) {
+        let bottom = self.bottom_row();
+        if bottom > self.lowest_row {
+            self.lowest_row = bottom;
+            self.lock_resets = 0;
+        }
+    }
+
+    // Chamado após um shift ou rotação bem-sucedido
+    fn on_moved(&mut self, was_grounded: bool) {
+        self.track_lowest_row();
+        if !was_grounded {
+            return;
+        }
+
+        if self.lock_resets < self.max_lock_resets {
+            self.lock_timer = Duration::ZERO;
+            self.lock_resets += 1;
+        } else if self.is_grounded() {
+            self.lock_piece();
         }
     }
 
     pub fn shift(&mut self, direction: Direction) {
-        if self.game_over {
+        if self.is_game_over() {
             return;
         }
 
@@ -122,73 +618,218 @@ impl Tetris {
         if !self.is_out_of_bounds(&translated_curr_shape)
             && !self.is_colliding(&translated_curr_shape)
         {
+            let was_grounded = self.is_grounded();
             self.curr_shape = translated_curr_shape;
+            self.last_move = Some(Movement::Shift);
+            self.events.push(GameEvent::Moved { direction });
+            self.on_moved(was_grounded);
         }
     }
 
     pub fn rotate(&mut self) {
-        if self.game_over {
+        let rotated_curr_shape = self.curr_shape.rotated();
+        self.try_rotate(rotated_curr_shape);
+    }
+
+    pub fn rotate_ccw(&mut self) {
+        let rotated_curr_shape = self.curr_shape.rotated_ccw();
+        self.try_rotate(rotated_curr_shape);
+    }
+
+    pub fn rotate_180(&mut self) {
+        let rotated_curr_shape = self.curr_shape.rotated_180();
+        self.try_rotate(rotated_curr_shape);
+    }
+
+    fn try_rotate(&mut self, rotated_curr_shape: Shape) {
+        if self.is_game_over() {
             return;
         }
 
-        let rotated_curr_shape = self.curr_shape.rotated();
+        let kicks = rotation::kicks(
+            self.curr_shape.kind(),
+            self.curr_shape.rotation(),
+            rotated_curr_shape.rotation(),
+        );
+        let half_turn = rotated_curr_shape.rotation() == self.curr_shape.rotation().flipped();
 
-        if !self.is_out_of_bounds(&rotated_curr_shape) && !self.is_colliding(&rotated_curr_shape) {
-            self.curr_shape = rotated_curr_shape;
+        // Tenta cada deslocamento da tabela SRS até achar uma posição livre
+        for (i, &kick) in kicks.iter().enumerate() {
+            let kicked_shape = &rotated_curr_shape + kick;
+            if !self.is_out_of_bounds(&kicked_shape) && !self.is_colliding(&kicked_shape) {
+                let was_grounded = self.is_grounded();
+                self.curr_shape = kicked_shape;
+                self.last_move = Some(Movement::Rotate { kick: i, half_turn });
+                self.events.push(GameEvent::Rotated {
+                    rotation: self.curr_shape.rotation(),
+                    kick: i,
+                });
+                self.on_moved(was_grounded);
+                return;
+            }
         }
     }
 
+    pub fn hold(&mut self) {
+        if !self.can_hold() {
+            return;
+        }
+
+        // A peça guardada volta à orientação e posição de nascimento
+        let stashed = Shape::new(self.curr_shape.kind());
+        self.events.push(GameEvent::Hold {
+            kind: stashed.kind(),
+        });
+        let next_shape = match self.held_shape.replace(stashed) {
+            Some(held) => held,
+            None => self.next_shape(),
+        };
+        self.spawn(next_shape);
+        self.hold_used = true;
+    }
+
+    #[allow(clippy::should_implement_trait)]
     pub fn drop(&mut self) {
-        if self.game_over {
+        if self.is_game_over() {
             return;
         }
 
-        while !self.is_out_of_bounds(&(&self.curr_shape + Pos(0, 1)))
-            && !self.is_colliding(&(&self.curr_shape + Pos(0, 1)))
+        let landing_shape = self.landing_shape();
+        let landing_row = landing_shape.iter_positions().map(|pos| pos.1).max().unwrap_or(0);
+        let cells = (landing_row - self.bottom_row()) as u32;
+        self.score += self.scoring.hard_drop(cells);
+        if cells > 0 {
+            self.last_move = Some(Movement::Fall);
+        }
+        self.events.push(GameEvent::HardDropped { cells });
+        self.curr_shape = landing_shape;
+        self.lock_piece(); // Hard drop ignora o lock delay
+    }
+
+    // Onde a peça atual pararia com um hard drop
+    pub fn ghost_positions(&self) -> Vec<Pos> {
+        self.landing_shape().iter_positions().collect()
+    }
+
+    fn landing_shape(&self) -> Shape {
+        let mut shape = self.curr_shape.clone();
+        while !self.is_out_of_bounds(&(&shape + Pos(0, 1)))
+            && !self.is_colliding(&(&shape + Pos(0, 1)))
         {
-            self.curr_shape = &self.curr_shape + Pos(0, 1);
+            shape = &shape + Pos(0, 1);
         }
-        self.tick(); // Finaliza a peça
+        shape
     }
 
-    pub fn is_line_full(&self, y: i32) -> bool {
-        self.fixed_shapes
-            .iter()
-            .flat_map(|shape| shape.iter_positions())
-            .filter(|pos| pos.1 == y)
-            .collect::<HashSet<_>>()
-            .len()
-            == self.width as usize
+    fn next_shape(&mut self) -> Shape {
+        let shape = match self.queue.pop_front() {
+            Some(shape) => shape,
+            None => Shape::new(self.randomizer.next_piece(&mut self.rng)),
+        };
+        self.fill_queue();
+        shape
     }
 
-    fn remove_line(&mut self, y: i32) {
-        for shape in self.fixed_shapes.iter_mut() {
-            shape.remove_line(y)
+    // Guideline: a caixa da peça nasce nas duas linhas logo acima do campo,
+    // com peças de 3 colunas puxadas para a esquerda (colunas 3-5 num campo de
+    // 10), e desce uma linha na hora se houver espaço
+    fn spawn(&mut self, shape: Shape) {
+        let spawn_row = -self.board.buffer().min(2);
+        self.curr_shape = &shape + Pos((self.width - 4) / 2, spawn_row);
+        if self.is_colliding(&self.curr_shape) {
+            self.end_game(GameOverReason::BlockOut);
+            return;
+        }
+
+        let lowered_shape = &self.curr_shape + Pos(0, 1);
+        if spawn_row < 0
+            && !self.is_out_of_bounds(&lowered_shape)
+            && !self.is_colliding(&lowered_shape)
+        {
+            self.curr_shape = lowered_shape;
         }
+        self.reset_lock_state();
+        self.events.push(GameEvent::PieceSpawned { kind: shape.kind() });
     }
 
-    fn remove_full_lines(&mut self) {
-        let mut lines_removed = 0;
-        for line in 0..self.height {
-            if self.is_line_full(line) {
-                self.remove_line(line);
-                lines_removed += 1;
-            }
+    fn fill_queue(&mut self) {
+        while self.queue.len() < self.preview_len {
+            let shape = Shape::new(self.randomizer.next_piece(&mut self.rng));
+            self.queue.push_back(shape);
         }
-        // Pontuação: 100 por linha, com bônus para múltiplas linhas
-        self.score += match lines_removed {
-            1 => 100,
-            2 => 300,
-            3 => 500,
-            4 => 800,
-            _ => 0,
+    }
+
+    pub fn is_line_full(&self, y: i32) -> bool {
+        self.board.is_line_full(y)
+    }
+
+    // Devolve as linhas removidas, de cima para baixo
+    fn remove_full_lines(&mut self) -> Vec<i32> {
+        let rows = (self.board.top()..self.height)
+            .filter(|&y| self.board.is_line_full(y))
+            .collect();
+        self.board.clear_full_lines();
+        rows
+    }
+
+    fn score_lines(&mut self, rows: Vec<i32>, spin: Spin) {
+        let lines_removed = rows.len() as u32;
+        // Uma peça travada sem limpar linhas quebra o combo
+        let combo = match lines_removed {
+            0 => None,
+            _ => Some(self.combo.map_or(0, |combo| combo + 1)),
+        };
+        self.combo = combo;
+
+        let Some(kind) = ClearKind::new(lines_removed, spin) else {
+            return;
+        };
+
+        let clear = Clear {
+            kind,
+            level: self.level(),
+            combo: combo.unwrap_or(0),
+            back_to_back: self.back_to_back && kind.is_difficult(),
+            perfect_clear: lines_removed > 0 && self.board.is_empty(),
         };
+        let breakdown = self.scoring.clear(&clear);
+
+        self.stats.tetrises += (kind == ClearKind::Tetris) as u32;
+        self.stats.t_spins += (kind.spin() != Spin::None) as u32;
+        self.stats.perfect_clears += clear.perfect_clear as u32;
+        self.stats.max_combo = self.stats.max_combo.max(clear.combo);
+
+        if lines_removed > 0 {
+            self.events.push(GameEvent::LinesCleared { rows, kind });
+        }
+        self.events.push(GameEvent::Scored(breakdown));
+
+        let level = self.level();
+        self.score += breakdown.total;
+        self.lines += lines_removed;
+        if self.level() > level {
+            self.events.push(GameEvent::LevelUp {
+                level: self.level(),
+            });
+        }
+        // T-spins sem linhas não quebram nem iniciam back-to-back
+        if lines_removed > 0 {
+            self.back_to_back = kind.is_difficult();
+        }
+        self.last_clear = Some(breakdown);
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Tetris;
+    use super::{Direction, GameOverReason, Movement, Tetris};
+    use crate::engine::piece::{Cell, PieceKind};
+    use crate::engine::position::Pos;
+    use crate::engine::rotation::Rotation;
+    use crate::engine::scoring::{Classic, ClearKind, Spin};
+    use crate::engine::shape::Shape;
+    use crate::interface::event::GameEvent;
+    use std::time::Duration;
 
     #[test]
     fn tests() {
@@ -198,4 +839,308 @@ mod tests {
         tetris.tick();
         println!("{:#?}", tetris);
     }
+
+    #[test]
+    fn same_seed_and_inputs_give_identical_games() {
+        let play = |seed| {
+            let mut tetris = Tetris::with_seed(10, 20, seed);
+            for i in 0..200 {
+                match i % 5 {
+                    0 => tetris.rotate(),
+                    1 => tetris.shift(Direction::Left),
+                    2 => tetris.drop(),
+                    _ => tetris.tick(),
+                }
+            }
+            let cells: Vec<_> = tetris.iter_position().map(|pos| tetris.get(pos)).collect();
+            (cells, tetris.score(), tetris.is_game_over())
+        };
+
+        assert_eq!(play(42), play(42));
+        assert_eq!(Tetris::with_seed(10, 20, 42).seed(), 42);
+    }
+
+    #[test]
+    fn restored_snapshot_continues_the_same_game() {
+        let step = |tetris: &mut Tetris, i: u32| match i % 6 {
+            0 => tetris.rotate(),
+            1 => tetris.shift(Direction::Right),
+            2 => tetris.hold(),
+            3 => tetris.drop(),
+            4 => tetris.update(Duration::from_millis(120)),
+            _ => tetris.soft_drop(),
+        };
+
+        let mut tetris = Tetris::with_seed(10, 20, 3);
+        tetris.set_scoring_rule(Box::new(Classic));
+        for i in 0..40 {
+            step(&mut tetris, i);
+        }
+        let saved = tetris.snapshot().to_string();
+        let mut restored = Tetris::restore(&saved.parse().unwrap()).unwrap();
+        assert_eq!(restored.snapshot().to_string(), saved);
+
+        for i in 40..160 {
+            step(&mut tetris, i);
+            step(&mut restored, i);
+        }
+        assert_eq!(restored.snapshot(), tetris.snapshot());
+    }
+
+    #[test]
+    fn snapshots_with_unsupported_sizes_are_rejected() {
+        let saved = Tetris::with_seed(10, 20, 3).snapshot();
+        for size in ["10 100000 20", "3 20 20", "65 20 20", "10 20 4294967295", "10 4294967295 1"] {
+            let mut snapshot = saved.clone();
+            snapshot.set("size", size);
+            assert_eq!(Tetris::restore(&snapshot).err(), Some(snapshot.invalid("size")));
+        }
+    }
+
+    #[test]
+    fn next_pieces_become_the_current_piece() {
+        let mut tetris = Tetris::with_seed(10, 20, 7);
+        tetris.set_preview_len(3);
+        let upcoming: Vec<_> = tetris.next_pieces().map(|shape| shape.kind()).collect();
+        assert_eq!(upcoming.len(), 3);
+
+        tetris.drop();
+        assert_eq!(tetris.curr_shape.kind(), upcoming[0]);
+        assert_eq!(tetris.next_pieces().next().unwrap().kind(), upcoming[1]);
+    }
+
+    #[test]
+    fn hold_swaps_once_per_piece() {
+        let mut tetris = Tetris::with_seed(10, 20, 3);
+        let first = tetris.curr_shape.kind();
+        let second = tetris.next_pieces().next().unwrap().kind();
+
+        tetris.rotate();
+        tetris.hold();
+        assert_eq!(tetris.held_piece().unwrap().kind(), first);
+        assert_eq!(tetris.held_piece().unwrap().rotation(), Rotation::Spawn);
+        assert_eq!(tetris.curr_shape.kind(), second);
+
+        // Segundo hold antes de travar a peça é ignorado
+        tetris.hold();
+        assert_eq!(tetris.curr_shape.kind(), second);
+
+        tetris.drop();
+        let third = tetris.curr_shape.kind();
+        tetris.hold();
+        assert_eq!(tetris.held_piece().unwrap().kind(), third);
+        assert_eq!(tetris.curr_shape.kind(), first);
+    }
+
+    #[test]
+    fn ghost_matches_hard_drop_landing() {
+        let mut tetris = Tetris::with_seed(10, 20, 11);
+        tetris.shift(Direction::Left);
+        let mut ghost = tetris.ghost_positions();
+        ghost.sort_by_key(|pos| (pos.1, pos.0));
+
+        // Campo vazio: nenhuma linha sai, e só a peça travada tem esse tipo
+        let kind = tetris.curr_shape.kind();
+        tetris.drop();
+        let mut landed: Vec<_> = tetris
+            .iter_position()
+            .filter(|&pos| tetris.get(pos) == Cell::Piece(kind))
+            .collect();
+        landed.sort_by_key(|pos| (pos.1, pos.0));
+        assert_eq!(landed.len(), 4);
+        assert_eq!(ghost, landed);
+    }
+
+    #[test]
+    fn lock_delay_waits_on_the_ground_and_resets_on_moves() {
+        let mut tetris = Tetris::with_seed(10, 20, 5);
+        tetris.set_max_lock_resets(2);
+        while !tetris.is_grounded() {
+            tetris.tick();
+        }
+
+        tetris.tick();
+        tetris.update(Duration::from_millis(400));
+        assert_eq!(tetris.statistics().pieces, 0);
+
+        // Cada movimento no chão zera o timer, até esgotar os resets
+        tetris.shift(Direction::Left);
+        tetris.update(Duration::from_millis(400));
+        tetris.shift(Direction::Right);
+        tetris.update(Duration::from_millis(400));
+        assert_eq!(tetris.statistics().pieces, 0);
+
+        tetris.shift(Direction::Left);
+        assert_eq!(tetris.statistics().pieces, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 64 columns wide, got 65")]
+    fn boards_wider_than_the_bitboard_are_rejected() {
+        Tetris::with_seed(65, 20, 1);
+    }
+
+    #[test]
+    fn gravity_speeds_up_with_level() {
+        let mut tetris = Tetris::with_seed(10, 20, 1);
+        assert_eq!(tetris.level(), 1);
+        assert!((tetris.gravity() - 1.0 / 60.0).abs() < 1e-9);
+
+        let mut previous = tetris.gravity();
+        for level in 2..=20 {
+            tetris.lines = (level - 1) * super::LINES_PER_LEVEL;
+            assert_eq!(tetris.level(), level);
+            assert!(tetris.gravity() > previous || tetris.gravity() == super::MAX_GRAVITY);
+            previous = tetris.gravity();
+        }
+        assert_eq!(tetris.gravity(), super::MAX_GRAVITY);
+
+        // Onde a fórmula ficaria negativa
+        tetris.lines = 1150;
+        assert_eq!(tetris.level(), 116);
+        assert_eq!(tetris.gravity(), super::MAX_GRAVITY);
+    }
+
+    #[test]
+    fn rotating_into_a_covered_slot_scores_a_t_spin_double() {
+        let mut tetris = Tetris::with_seed(5, 6, 9);
+        for pos in [Pos(0, 5), Pos(1, 5), Pos(3, 5), Pos(4, 5), Pos(0, 4), Pos(4, 4), Pos(1, 3)] {
+            tetris.board.set(pos, Cell::Garbage);
+        }
+
+        // T apontando para a direita, logo acima do encaixe
+        tetris.curr_shape = &Shape::new_t().rotated() + Pos(1, 3);
+        tetris.rotate();
+        tetris.drop();
+
+        let clear = tetris.last_clear().unwrap();
+        assert_eq!(clear.kind, ClearKind::TSpinDouble);
+        assert_eq!(tetris.score(), 1200);
+        assert_eq!(tetris.lines_cleared(), 2);
+    }
+
+    #[test]
+    fn only_quarter_turn_tst_kicks_upgrade_a_mini_t_spin() {
+        let mut tetris = Tetris::with_seed(5, 6, 9);
+        // T para cima com os dois cantos de trás e um da frente ocupados
+        tetris.curr_shape = &Shape::new_t() + Pos(1, 3);
+        let center = tetris.curr_shape.center();
+        for offset in [Pos(-1, 1), Pos(1, 1), Pos(-1, -1)] {
+            tetris.board.set(center + offset, Cell::Garbage);
+        }
+
+        tetris.last_move = Some(Movement::Rotate { kick: 3, half_turn: false });
+        assert_eq!(tetris.detect_spin(), Spin::Mini);
+        tetris.last_move = Some(Movement::Rotate { kick: 4, half_turn: false });
+        assert_eq!(tetris.detect_spin(), Spin::Full);
+        tetris.last_move = Some(Movement::Rotate { kick: 4, half_turn: true });
+        assert_eq!(tetris.detect_spin(), Spin::Mini);
+    }
+
+    #[test]
+    fn clearing_every_cell_is_a_perfect_clear() {
+        let mut tetris = Tetris::with_seed(4, 6, 2);
+        tetris.set_scoring_rule(Box::new(Classic));
+        for pos in [Pos(0, 4), Pos(1, 4), Pos(0, 5), Pos(1, 5)] {
+            tetris.board.set(pos, Cell::Garbage);
+        }
+        tetris.curr_shape = &Shape::new_o() + Pos(1, 0);
+
+        tetris.drop();
+        assert!(tetris.last_clear().unwrap().perfect_clear);
+        assert_eq!(tetris.statistics().perfect_clears, 1);
+        assert_eq!(tetris.score(), 300);
+    }
+
+    #[test]
+    fn events_describe_a_line_clear_in_order() {
+        let mut tetris = Tetris::with_seed(4, 6, 2);
+        for pos in [Pos(0, 4), Pos(1, 4), Pos(0, 5), Pos(1, 5)] {
+            tetris.board.set(pos, Cell::Garbage);
+        }
+        tetris.curr_shape = &Shape::new_o() + Pos(1, 0);
+        tetris.drain_events().for_each(drop);
+
+        tetris.shift(Direction::Left);
+        tetris.shift(Direction::Right);
+        tetris.shift(Direction::Right); // Parede: não gera evento
+        tetris.drop();
+
+        let events: Vec<GameEvent> = tetris.drain_events().collect();
+        assert_eq!(events[0], GameEvent::Moved { direction: Direction::Left });
+        assert_eq!(events[1], GameEvent::Moved { direction: Direction::Right });
+        assert_eq!(events[2], GameEvent::HardDropped { cells: 4 });
+        assert_eq!(
+            events[3],
+            GameEvent::Locked {
+                kind: PieceKind::O,
+                spin: Spin::None
+            }
+        );
+        assert_eq!(
+            events[4],
+            GameEvent::LinesCleared {
+                rows: vec![4, 5],
+                kind: ClearKind::Double
+            }
+        );
+        assert!(matches!(events[5], GameEvent::Scored(breakdown) if breakdown.perfect_clear));
+        assert!(matches!(events[6], GameEvent::PieceSpawned { .. }));
+        assert_eq!(events.len(), 7);
+        assert_eq!(tetris.drain_events().count(), 0);
+    }
+
+    #[test]
+    fn rotation_kicks_off_the_left_wall() {
+        let mut tetris = Tetris::new(10, 20);
+        // T em estado R encostado na parede esquerda
+        tetris.curr_shape = &Shape::new_t().rotated() + Pos(-1, 5);
+        tetris.rotate();
+
+        assert_eq!(tetris.curr_shape.rotation(), Rotation::Half);
+        for pos in [Pos(0, 6), Pos(1, 6), Pos(2, 6), Pos(1, 7)] {
+            assert!(tetris.curr_shape.has_position(pos));
+        }
+    }
+
+    #[test]
+    fn ccw_and_180_rotations_compose() {
+        let mut tetris = Tetris::new(10, 20);
+        tetris.curr_shape = &Shape::new_j() + Pos(3, 5);
+        let start = tetris.curr_shape.clone();
+
+        tetris.rotate_180();
+        assert_eq!(tetris.curr_shape.rotation(), Rotation::Half);
+        tetris.rotate_ccw();
+        tetris.rotate_ccw();
+        assert_eq!(tetris.curr_shape.rotation(), Rotation::Spawn);
+        assert!(start.iter_positions().all(|pos| tetris.curr_shape.has_position(pos)));
+    }
+
+    #[test]
+    fn pieces_spawn_in_the_buffer_and_report_why_the_game_ended() {
+        let mut tetris = Tetris::new(10, 20);
+        tetris.spawn(Shape::new_t());
+        // Nasce nas linhas 21-22 e desce uma logo em seguida
+        for pos in [Pos(4, -1), Pos(3, 0), Pos(4, 0), Pos(5, 0)] {
+            assert!(tetris.curr_shape.has_position(pos));
+        }
+
+        // Sem espaço para descer, o O trava inteiro na zona oculta
+        for x in 0..9 {
+            tetris.board.set(Pos(x, 0), Cell::Garbage);
+        }
+        tetris.curr_shape = &Shape::new_o() + Pos(3, -2);
+        tetris.drop();
+        assert_eq!(tetris.game_over_reason(), Some(GameOverReason::LockOut));
+
+        tetris.reset();
+        for x in 3..7 {
+            tetris.board.set(Pos(x, -2), Cell::Garbage);
+            tetris.board.set(Pos(x, -1), Cell::Garbage);
+        }
+        tetris.spawn(Shape::new_i());
+        assert_eq!(tetris.game_over_reason(), Some(GameOverReason::BlockOut));
+        assert!(!tetris.can_hold());
+    }
 }
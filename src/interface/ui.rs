@@ -1,16 +1,335 @@
-// UI module - currently not used
-// This module was intended for WASM/web interface
-// The game currently uses SDL2 backend (see main.rs)
+use crate::engine::piece::{Cell, PieceKind};
+use crate::engine::position::Pos;
+use crate::engine::scoring::ScoreBreakdown;
+use crate::engine::shape::Shape;
+use crate::interface::event::GameEvent;
+use crate::interface::game::FRAME;
+use crate::interface::palette::Rgb;
+use crate::interface::tetris::{GameOverReason, Tetris};
 
-#[allow(dead_code)]
-pub struct App {
-    width: u32,
-    height: u32,
+// Duração do flash das linhas limpas, em frames (~150 ms)
+pub const FLASH_FRAMES: u64 = 9;
+// Por quantos frames um aviso como "Level Up" fica na tela
+pub const BANNER_FRAMES: u64 = 2 * 60;
+
+pub fn format_time(frames: u64) -> String {
+    let seconds = frames * FRAME.as_micros() as u64 / 1_000_000;
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+// Quebra o texto entre palavras em linhas de até `max_chars` caracteres;
+// uma palavra mais longa que isso fica sozinha na linha
+pub fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= max_chars => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines
+}
+
+/// O que aparece numa célula visível do campo.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Tile {
+    Cell(Cell),
+    Ghost(PieceKind),
+}
+
+/// Cor de destaque de estados e avisos; cada front-end a traduz.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Tone {
+    Info,
+    Good,
+    Bad,
+    Highlight,
+}
+
+impl Tone {
+    pub fn color(self) -> Rgb {
+        match self {
+            Tone::Info => Rgb(59, 130, 246),
+            Tone::Good => Rgb(16, 185, 129),
+            Tone::Bad => Rgb(239, 68, 68),
+            Tone::Highlight => Rgb(255, 215, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Banner {
+    pub text: String,
+    pub tone: Tone,
+}
+
+/// Se o quadro é de uma partida, de um replay ou de uma partida salva à
+/// espera de Y/N para continuar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Playing,
+    Continue,
+    Replay {
+        paused: bool,
+        speed: f64,
+        total_frames: u64,
+    },
+}
+
+/// Tudo o que um front-end precisa para desenhar um quadro, sem olhar o
+/// `Tetris` diretamente. Montado por `View::frame`.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub width: i32,
+    pub height: i32,
+    tiles: Vec<Tile>,
+    // Linhas que acabaram de ser limpas
+    pub flash_rows: Vec<i32>,
+    pub hold: Option<Shape>,
+    pub next: Vec<Shape>,
+    pub score: u32,
+    pub level: u32,
+    pub lines: u32,
+    // Frames desde o início da partida
+    pub frame: u64,
+    pub last_clear: Option<ScoreBreakdown>,
+    pub game_over: Option<GameOverReason>,
+    pub mode: Mode,
+    pub banners: Vec<Banner>,
+    pub controls: Vec<String>,
+}
+
+impl Frame {
+    pub fn tile(&self, pos: Pos) -> Tile {
+        let Pos(x, y) = pos;
+        self.tiles[(y * self.width + x) as usize]
+    }
+
+    pub fn time(&self) -> String {
+        format_time(self.frame)
+    }
+
+    // Linha de estado: jogando, fim de jogo ou posição do replay
+    pub fn status(&self) -> (String, Tone) {
+        match (self.mode, self.game_over) {
+            (
+                Mode::Replay {
+                    paused,
+                    speed,
+                    total_frames,
+                },
+                _,
+            ) => (
+                format!(
+                    "{} {}x {} / {}",
+                    if paused { "PAUSED" } else { "REPLAY" },
+                    speed,
+                    self.time(),
+                    format_time(total_frames),
+                ),
+                Tone::Info,
+            ),
+            (Mode::Continue, _) => ("SAVED GAME".to_string(), Tone::Info),
+            (Mode::Playing, Some(reason)) => (format!("GAME OVER! ({})", reason), Tone::Bad),
+            (Mode::Playing, None) => ("Playing...".to_string(), Tone::Good),
+        }
+    }
+}
+
+/// Desenha quadros prontos; implementado pelas versões SDL2 e terminal.
+pub trait Renderer {
+    type Error;
+
+    fn render(&mut self, frame: &Frame) -> Result<(), Self::Error>;
+}
+
+/// Estado da interface que dura entre quadros: peça fantasma ligada, flash
+/// de linhas e avisos. Os prazos contam em frames de jogo.
+#[derive(Debug)]
+pub struct View {
+    show_ghost: bool,
+    controls: Vec<String>,
+    flash_rows: Vec<i32>,
+    flash_until: u64,
+    banners: Vec<(Banner, u64)>,
+}
+
+impl View {
+    pub fn new(controls: Vec<String>) -> Self {
+        Self {
+            show_ghost: true,
+            controls,
+            flash_rows: Vec::new(),
+            flash_until: 0,
+            banners: Vec::new(),
+        }
+    }
+
+    pub fn toggle_ghost(&mut self) {
+        self.show_ghost = !self.show_ghost;
+    }
+
+    // Eventos drenados do jogo no frame `frame`
+    pub fn update(&mut self, events: impl IntoIterator<Item = GameEvent>, frame: u64) {
+        for event in events {
+            match event {
+                GameEvent::LinesCleared { rows, .. } => {
+                    self.flash_rows = rows;
+                    self.flash_until = frame + FLASH_FRAMES;
+                }
+                GameEvent::Scored(breakdown) if breakdown.perfect_clear => {
+                    self.banner("PERFECT CLEAR", Tone::Highlight, frame);
+                }
+                GameEvent::LevelUp { level } => {
+                    self.banner(&format!("Level Up! Level {}", level), Tone::Good, frame);
+                }
+                // O relógio volta a zero, e os prazos antigos não valeriam
+                GameEvent::Reset => {
+                    self.flash_rows.clear();
+                    self.banners.clear();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn banner(&mut self, text: &str, tone: Tone, frame: u64) {
+        let banner = Banner {
+            text: text.to_string(),
+            tone,
+        };
+        self.banners.push((banner, frame + BANNER_FRAMES));
+    }
+
+    pub fn frame(&mut self, tetris: &Tetris, frame: u64, mode: Mode) -> Frame {
+        if frame >= self.flash_until {
+            self.flash_rows.clear();
+        }
+        self.banners.retain(|&(_, until)| frame < until);
+
+        let ghost = match self.show_ghost && !tetris.is_game_over() {
+            true => tetris.ghost_positions(),
+            false => vec![],
+        };
+        let ghost_kind = tetris.current_piece().kind();
+        let tiles = tetris
+            .iter_position()
+            .map(|pos| match tetris.get(pos) {
+                Cell::Empty if ghost.contains(&pos) => Tile::Ghost(ghost_kind),
+                cell => Tile::Cell(cell),
+            })
+            .collect();
+
+        let mut banners: Vec<Banner> =
+            self.banners.iter().map(|(banner, _)| banner.clone()).collect();
+        // A pergunta fica na tela até a resposta, sem prazo
+        if mode == Mode::Continue {
+            banners.push(Banner {
+                text: "Continue? Y/N".to_string(),
+                tone: Tone::Highlight,
+            });
+        }
+
+        Frame {
+            width: tetris.width(),
+            height: tetris.height(),
+            tiles,
+            flash_rows: self.flash_rows.iter().copied().filter(|&y| y >= 0).collect(),
+            hold: tetris.held_piece().cloned(),
+            next: tetris.next_pieces().cloned().collect(),
+            score: tetris.score(),
+            level: tetris.level(),
+            lines: tetris.lines_cleared(),
+            frame,
+            last_clear: tetris.last_clear().copied(),
+            game_over: tetris.game_over_reason(),
+            mode,
+            banners,
+            controls: self.controls.clone(),
+        }
+    }
 }
 
-impl App {
-    #[allow(dead_code)]
-    pub fn new(width: u32, height: u32) -> Self {
-        Self { width, height }
+#[cfg(test)]
+mod tests {
+    use super::{wrap, Mode, Tile, Tone, View, BANNER_FRAMES, FLASH_FRAMES};
+    use crate::engine::position::Pos;
+    use crate::engine::scoring::ClearKind;
+    use crate::interface::event::GameEvent;
+    use crate::interface::tetris::Tetris;
+
+    fn ghost_tiles(view: &mut View, tetris: &Tetris) -> usize {
+        let frame = view.frame(tetris, 0, Mode::Playing);
+        tetris
+            .iter_position()
+            .filter(|&pos| matches!(frame.tile(pos), Tile::Ghost(_)))
+            .count()
+    }
+
+    #[test]
+    fn frames_mirror_the_game() {
+        let tetris = Tetris::with_seed(10, 20, 4);
+        let mut view = View::new(vec!["q - Quit".to_string()]);
+        let frame = view.frame(&tetris, 0, Mode::Playing);
+        assert_eq!((frame.width, frame.height), (10, 20));
+        assert_eq!(frame.next.len(), tetris.next_pieces().count());
+        assert_eq!(frame.status(), ("Playing...".to_string(), Tone::Good));
+        assert_eq!(frame.tile(Pos(0, 19)), Tile::Cell(tetris.get(Pos(0, 19))));
+
+        assert_eq!(ghost_tiles(&mut view, &tetris), 4);
+        view.toggle_ghost();
+        assert_eq!(ghost_tiles(&mut view, &tetris), 0);
+    }
+
+    #[test]
+    fn text_wraps_between_words() {
+        assert_eq!(wrap("GAME OVER! (Block Out)", 16), ["GAME OVER!", "(Block Out)"]);
+        assert_eq!(wrap("Tetris  +800", 12), ["Tetris +800"]);
+        assert_eq!(wrap("Back-to-Back", 4), ["Back-to-Back"]);
+        assert!(wrap("", 10).is_empty());
+    }
+
+    #[test]
+    fn flashes_and_banners_expire() {
+        let tetris = Tetris::with_seed(10, 20, 4);
+        let mut view = View::new(vec![]);
+        let events = [
+            GameEvent::LinesCleared {
+                rows: vec![18, 19],
+                kind: ClearKind::Double,
+            },
+            GameEvent::LevelUp { level: 2 },
+        ];
+        view.update(events, 100);
+
+        let frame = view.frame(&tetris, 100, Mode::Playing);
+        assert_eq!(frame.flash_rows, [18, 19]);
+        assert_eq!(frame.banners[0].text, "Level Up! Level 2");
+
+        let frame = view.frame(&tetris, 100 + FLASH_FRAMES, Mode::Playing);
+        assert!(frame.flash_rows.is_empty());
+        assert_eq!(frame.banners.len(), 1);
+        let frame = view.frame(&tetris, 100 + BANNER_FRAMES, Mode::Playing);
+        assert!(frame.banners.is_empty());
+
+        // Depois de um reinício o relógio volta a zero
+        view.update([GameEvent::LevelUp { level: 2 }, GameEvent::Reset], 500);
+        assert!(view.frame(&tetris, 0, Mode::Playing).banners.is_empty());
+    }
+
+    #[test]
+    fn saved_games_ask_to_continue() {
+        let tetris = Tetris::with_seed(10, 20, 4);
+        let mut view = View::new(vec![]);
+        for frame in [0, BANNER_FRAMES * 10] {
+            let frame = view.frame(&tetris, frame, Mode::Continue);
+            assert_eq!(frame.status(), ("SAVED GAME".to_string(), Tone::Info));
+            assert_eq!(frame.banners.len(), 1);
+            assert_eq!(frame.banners[0].tone, Tone::Highlight);
+        }
+        assert!(view.frame(&tetris, 0, Mode::Playing).banners.is_empty());
     }
 }
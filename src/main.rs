@@ -1,38 +1,42 @@
 use sdl2::event::Event;
 use sdl2::keyboard::Keycode;
-use sdl2::pixels::Color;
+use sdl2::pixels::{Color, PixelFormatEnum};
 use sdl2::rect::Rect;
-use sdl2::render::WindowCanvas;
-use std::time::Duration;
+use sdl2::render::{BlendMode, Texture, TextureCreator, WindowCanvas};
+use sdl2::video::WindowContext;
+use sdl2::EventPump;
+use std::fs;
+use std::time::{Duration, Instant};
+use tetris::engine::piece::{Cell, PieceKind};
 use tetris::engine::position::Pos;
-use tetris::interface::tetris::{Direction, Tetris};
+use tetris::engine::shape::Shape;
+use tetris::interface::font::{self, Align, ADVANCE, GLYPH_HEIGHT, GLYPH_WIDTH};
+use tetris::interface::game::{Game, Input};
+use tetris::interface::input::{Button, InputHandler};
+use tetris::interface::palette::{cell_color, Rgb};
+use tetris::interface::replay::{Player, Recorder, Replay, REPLAY_FILE, SEEK_FRAMES};
+use tetris::interface::snapshot::SAVE_FILE;
+use tetris::interface::ui::{self, Frame, Mode, Renderer, Tile, Tone, View};
 
 const CELL_SIZE: u32 = 30;
 const GRID_PADDING: i32 = 10;
+const PREVIEW_CELL_SIZE: u32 = 15;
 
-fn get_color_for_type(typ: &str) -> Color {
-    match typ {
-        "I" => Color::RGB(0, 212, 255),    // Ciano
-        "O" => Color::RGB(255, 215, 0),    // Amarelo
-        "T" => Color::RGB(168, 85, 247),   // Roxo
-        "S" => Color::RGB(16, 185, 129),   // Verde
-        "Z" => Color::RGB(239, 68, 68),    // Vermelho
-        "J" => Color::RGB(59, 130, 246),   // Azul
-        "L" => Color::RGB(249, 115, 22),   // Laranja
-        _ => Color::RGB(15, 52, 96),       // Vazio (azul escuro)
-    }
+fn get_color_for_type(cell: Cell) -> Color {
+    let Rgb(r, g, b) = cell_color(cell);
+    Color::RGB(r, g, b)
 }
 
-fn draw_cell(canvas: &mut WindowCanvas, pos: Pos, cell_type: &str, grid_offset_x: i32, grid_offset_y: i32) {
+fn draw_cell(canvas: &mut WindowCanvas, pos: Pos, cell: Cell, grid_offset_x: i32, grid_offset_y: i32) {
     let x = grid_offset_x + pos.0 * CELL_SIZE as i32;
     let y = grid_offset_y + pos.1 * CELL_SIZE as i32;
     
-    let color = get_color_for_type(cell_type);
+    let color = get_color_for_type(cell);
     canvas.set_draw_color(color);
     canvas.fill_rect(Rect::new(x + 1, y + 1, CELL_SIZE - 2, CELL_SIZE - 2)).unwrap();
     
     // Borda mais clara para células preenchidas
-    if cell_type != "empty" {
+    if !cell.is_empty() {
         let lighter = Color::RGB(
             color.r.saturating_add(40),
             color.g.saturating_add(40),
@@ -43,41 +47,354 @@ fn draw_cell(canvas: &mut WindowCanvas, pos: Pos, cell_type: &str, grid_offset_x
     }
 }
 
-fn draw_text(canvas: &mut WindowCanvas, text: &str, x: i32, y: i32, _size: u32) {
-    // Simulação simples de texto com retângulos (para demonstração)
-    // Em produção, você usaria SDL2_ttf
-    canvas.set_draw_color(Color::RGB(255, 255, 255));
-    canvas.fill_rect(Rect::new(x, y, text.len() as u32 * 8, 12)).unwrap();
+fn draw_ghost_cell(canvas: &mut WindowCanvas, pos: Pos, kind: PieceKind, grid_offset_x: i32, grid_offset_y: i32) {
+    let x = grid_offset_x + pos.0 * CELL_SIZE as i32;
+    let y = grid_offset_y + pos.1 * CELL_SIZE as i32;
+
+    // Apenas o contorno, na cor da peça atual
+    canvas.set_draw_color(get_color_for_type(Cell::Piece(kind)));
+    canvas.draw_rect(Rect::new(x + 2, y + 2, CELL_SIZE - 4, CELL_SIZE - 4)).unwrap();
+    canvas.draw_rect(Rect::new(x + 3, y + 3, CELL_SIZE - 6, CELL_SIZE - 6)).unwrap();
 }
 
-fn main() -> Result<(), String> {
-    let sdl_context = sdl2::init()?;
-    let video_subsystem = sdl_context.video()?;
+fn draw_mini_shape(canvas: &mut WindowCanvas, shape: &Shape, x: i32, y: i32) {
+    canvas.set_draw_color(get_color_for_type(Cell::Piece(shape.kind())));
+    for Pos(px, py) in shape.iter_positions() {
+        let cell_x = x + px * PREVIEW_CELL_SIZE as i32;
+        let cell_y = y + py * PREVIEW_CELL_SIZE as i32;
+        canvas
+            .fill_rect(Rect::new(cell_x, cell_y, PREVIEW_CELL_SIZE - 1, PREVIEW_CELL_SIZE - 1))
+            .unwrap();
+    }
+}
 
-    let grid_width = 10;
-    let grid_height = 20;
-    let info_panel_width = 250;
-    
-    let window_width = (grid_width * CELL_SIZE + 2 * GRID_PADDING as u32 + info_panel_width) as u32;
-    let window_height = (grid_height * CELL_SIZE + 2 * GRID_PADDING as u32) as u32;
+// Rasteriza a fonte embutida numa textura com todos os glifos lado a lado,
+// brancos sobre transparente; a cor vem do color mod na hora de desenhar
+fn load_font(texture_creator: &TextureCreator<WindowContext>) -> Result<Texture<'_>, String> {
+    let width = font::chars().count() as u32 * GLYPH_WIDTH;
+    let mut pixels = vec![0u8; (width * GLYPH_HEIGHT * 4) as usize];
+    for (i, c) in font::chars().enumerate() {
+        for y in 0..GLYPH_HEIGHT {
+            for x in (0..GLYPH_WIDTH).filter(|&x| font::pixel(c, x, y)) {
+                let offset = ((y * width + i as u32 * GLYPH_WIDTH + x) * 4) as usize;
+                pixels[offset..offset + 4].copy_from_slice(&[255; 4]);
+            }
+        }
+    }
 
-    let window = video_subsystem
-        .window("🎮 Tetris Game", window_width, window_height)
-        .position_centered()
-        .build()
+    let mut texture = texture_creator
+        .create_texture_static(PixelFormatEnum::RGBA32, width, GLYPH_HEIGHT)
         .map_err(|e| e.to_string())?;
-
-    let mut canvas = window
-        .into_canvas()
-        .accelerated()
-        .present_vsync()
-        .build()
+    texture
+        .update(None, &pixels, (width * 4) as usize)
         .map_err(|e| e.to_string())?;
+    texture.set_blend_mode(BlendMode::Blend);
+    Ok(texture)
+}
 
-    let mut event_pump = sdl_context.event_pump()?;
-    let mut tetris = Tetris::new(grid_width, grid_height);
-    let mut frame_count = 0;
-    let frames_per_tick = 30; // Tick a cada 30 frames (~500ms a 60fps)
+/// Altura em pixels (arredondada para um múltiplo da fonte), cor, alinhamento
+/// em relação ao `x` e, opcionalmente, a largura em que o texto quebra.
+#[derive(Debug, Clone, Copy)]
+struct TextStyle {
+    size: u32,
+    color: Color,
+    align: Align,
+    wrap: Option<u32>,
+}
+
+impl TextStyle {
+    fn new(size: u32, color: Color) -> Self {
+        Self {
+            size,
+            color,
+            align: Align::Left,
+            wrap: None,
+        }
+    }
+
+    fn align(self, align: Align) -> Self {
+        Self { align, ..self }
+    }
+
+    fn wrap(self, width: u32) -> Self {
+        Self {
+            wrap: Some(width),
+            ..self
+        }
+    }
+}
+
+// Devolve a altura ocupada, para empilhar textos quebrados em linhas
+fn draw_text(
+    canvas: &mut WindowCanvas,
+    font: &mut Texture,
+    text: &str,
+    x: i32,
+    y: i32,
+    style: TextStyle,
+) -> Result<u32, String> {
+    let scale = font::scale(style.size);
+    let lines = match style.wrap {
+        Some(width) => ui::wrap(text, font::columns(width, scale)),
+        None => vec![text.to_string()],
+    };
+    font.set_color_mod(style.color.r, style.color.g, style.color.b);
+
+    let line_height = font::line_height(scale);
+    for (row, line) in lines.iter().enumerate() {
+        let left = style.align.start(x, font::text_width(line, scale));
+        let top = y + (row as u32 * line_height) as i32;
+        for (col, c) in line.chars().enumerate() {
+            let src = Rect::new(
+                (font::glyph_index(c) as u32 * GLYPH_WIDTH) as i32,
+                0,
+                GLYPH_WIDTH,
+                GLYPH_HEIGHT,
+            );
+            let dst = Rect::new(
+                left + (col as u32 * ADVANCE * scale) as i32,
+                top,
+                GLYPH_WIDTH * scale,
+                GLYPH_HEIGHT * scale,
+            );
+            canvas.copy(font, src, dst)?;
+        }
+    }
+    Ok(lines.len() as u32 * line_height)
+}
+
+const PLAY_CONTROLS: [&str; 11] = [
+    "< > : Move",
+    "^ / W : Rotate",
+    "Z : Rotate CCW",
+    "A : Rotate 180",
+    "C : Hold",
+    "v : Soft Drop",
+    "Space : Hard Drop",
+    "G : Toggle Ghost",
+    "R : Restart",
+    "Q : Save & Quit",
+    "ESC : Quit",
+];
+
+const REPLAY_CONTROLS: [&str; 5] = [
+    "Space : Pause",
+    "^ v : Speed",
+    "< > : Seek 5s",
+    "G : Toggle Ghost",
+    "ESC : Quit",
+];
+
+fn input_for_key(keycode: Keycode) -> Option<Input> {
+    match keycode {
+        Keycode::Up | Keycode::W => Some(Input::RotateCw),
+        Keycode::Z => Some(Input::RotateCcw),
+        Keycode::A => Some(Input::Rotate180),
+        Keycode::C | Keycode::LShift => Some(Input::Hold),
+        Keycode::Space => Some(Input::HardDrop),
+        Keycode::R => Some(Input::Reset),
+        _ => None,
+    }
+}
+
+// Teclas seguradas ficam com o `InputHandler` (DAS, ARR e soft drop)
+fn button_for_key(keycode: Keycode) -> Option<Button> {
+    match keycode {
+        Keycode::Left => Some(Button::Left),
+        Keycode::Right => Some(Button::Right),
+        Keycode::Down => Some(Button::SoftDrop),
+        _ => None,
+    }
+}
+
+fn tone_color(tone: Tone) -> Color {
+    let Rgb(r, g, b) = tone.color();
+    Color::RGB(r, g, b)
+}
+
+fn draw_frame(canvas: &mut WindowCanvas, font: &mut Texture, frame: &Frame) -> Result<(), String> {
+    let grid_width = frame.width as u32;
+    let grid_height = frame.height as u32;
+
+    // Clear canvas
+    canvas.set_draw_color(Color::RGB(17, 17, 30));
+    canvas.clear();
+
+    let grid_offset_x = GRID_PADDING;
+    let grid_offset_y = GRID_PADDING;
+
+    // Draw grid background
+    canvas.set_draw_color(Color::RGB(26, 26, 46));
+    canvas.fill_rect(Rect::new(
+        grid_offset_x - 5,
+        grid_offset_y - 5,
+        grid_width * CELL_SIZE + 10,
+        grid_height * CELL_SIZE + 10,
+    ))?;
+
+    // Draw cells and ghost piece
+    for y in 0..frame.height {
+        for x in 0..frame.width {
+            let pos = Pos(x, y);
+            match frame.tile(pos) {
+                Tile::Cell(cell) => draw_cell(canvas, pos, cell, grid_offset_x, grid_offset_y),
+                Tile::Ghost(kind) => {
+                    draw_cell(canvas, pos, Cell::Empty, grid_offset_x, grid_offset_y);
+                    draw_ghost_cell(canvas, pos, kind, grid_offset_x, grid_offset_y);
+                }
+            }
+        }
+    }
+
+    // Flash nas linhas que acabaram de sair
+    canvas.set_draw_color(Color::RGB(255, 255, 255));
+    for &y in &frame.flash_rows {
+        canvas.fill_rect(Rect::new(
+            grid_offset_x,
+            grid_offset_y + y * CELL_SIZE as i32,
+            grid_width * CELL_SIZE,
+            CELL_SIZE,
+        ))?;
+    }
+
+    // Avisos (perfect clear, level up) empilhados no meio do campo
+    let banners_y = grid_offset_y + (grid_height * CELL_SIZE) as i32 / 2 - 20;
+    let well_center = grid_offset_x + (grid_width * CELL_SIZE) as i32 / 2;
+    for (i, banner) in frame.banners.iter().enumerate() {
+        let banner_y = banners_y + i as i32 * 50;
+        canvas.set_draw_color(tone_color(banner.tone));
+        canvas.fill_rect(Rect::new(grid_offset_x, banner_y, grid_width * CELL_SIZE, 40))?;
+        let style = TextStyle::new(14, Color::RGB(17, 17, 30)).align(Align::Center);
+        draw_text(canvas, font, &banner.text, well_center, banner_y + 13, style)?;
+    }
+
+    // Draw info panel
+    let info_x = (grid_width * CELL_SIZE) as i32 + GRID_PADDING + 30;
+    let info_y = GRID_PADDING;
+    let white = Color::RGB(255, 255, 255);
+    let gray = Color::RGB(200, 200, 200);
+
+    // Title
+    draw_text(canvas, font, "TETRIS", info_x, info_y, TextStyle::new(28, white))?;
+
+    // Score, com os valores alinhados à direita da caixa
+    canvas.set_draw_color(Color::RGB(100, 200, 255));
+    canvas.fill_rect(Rect::new(info_x, info_y + 60, 200, 115))?;
+    let stats = [
+        ("Score", frame.score.to_string()),
+        ("Level", frame.level.to_string()),
+        ("Lines", frame.lines.to_string()),
+        ("Time", frame.time()),
+    ];
+    for (i, (label, value)) in stats.iter().enumerate() {
+        let y = info_y + 72 + i as i32 * 25;
+        draw_text(canvas, font, label, info_x + 10, y, TextStyle::new(14, white))?;
+        let style = TextStyle::new(14, white).align(Align::Right);
+        draw_text(canvas, font, value, info_x + 190, y, style)?;
+    }
+
+    // Status
+    let (status, tone) = frame.status();
+    canvas.set_draw_color(tone_color(tone));
+    canvas.fill_rect(Rect::new(info_x, info_y + 185, 200, 40))?;
+    let style = TextStyle::new(14, white).wrap(180);
+    draw_text(canvas, font, &status, info_x + 10, info_y + 189, style)?;
+
+    // Last clear
+    if let Some(clear) = frame.last_clear {
+        let style = TextStyle::new(14, tone_color(Tone::Highlight)).wrap(210);
+        draw_text(canvas, font, &clear.to_string(), info_x, info_y + 235, style)?;
+    }
+
+    // Next pieces
+    let next_x = info_x + 220;
+    draw_text(canvas, font, "Next", next_x, info_y, TextStyle::new(14, gray))?;
+    for (i, shape) in frame.next.iter().enumerate() {
+        let piece_y = info_y + 30 + i as i32 * 3 * PREVIEW_CELL_SIZE as i32;
+        draw_mini_shape(canvas, shape, next_x, piece_y);
+    }
+
+    // Hold box
+    let hold_y = info_y + 360;
+    draw_text(canvas, font, "Hold", next_x, hold_y, TextStyle::new(14, gray))?;
+    canvas.set_draw_color(Color::RGB(26, 26, 46));
+    canvas.fill_rect(Rect::new(
+        next_x - 5,
+        hold_y + 25,
+        4 * PREVIEW_CELL_SIZE + 10,
+        2 * PREVIEW_CELL_SIZE + 10,
+    ))?;
+    if let Some(shape) = &frame.hold {
+        draw_mini_shape(canvas, shape, next_x, hold_y + 30);
+    }
+
+    // Controls info, abaixo da última limpeza (até quatro linhas)
+    let controls_y = info_y + 310;
+    draw_text(canvas, font, "Controls:", info_x, controls_y, TextStyle::new(14, gray))?;
+    for (i, line) in frame.controls.iter().enumerate() {
+        let y = controls_y + 25 + i as i32 * 20;
+        draw_text(canvas, font, line, info_x, y, TextStyle::new(14, gray))?;
+    }
+
+    Ok(())
+}
+
+/// Janela SDL2 que desenha os quadros montados pela `ui`, com a textura da
+/// fonte embutida.
+struct SdlRenderer<'a> {
+    canvas: WindowCanvas,
+    font: Texture<'a>,
+}
+
+impl Renderer for SdlRenderer<'_> {
+    type Error = String;
+
+    fn render(&mut self, frame: &Frame) -> Result<(), String> {
+        draw_frame(&mut self.canvas, &mut self.font, frame)?;
+        self.canvas.present();
+        Ok(())
+    }
+}
+
+const CONTINUE_CONTROLS: [&str; 2] = ["Y : Continue", "N / ESC : New Game"];
+
+// Pergunta se a partida salva deve continuar; `None` se a janela fechar
+fn ask_continue(
+    renderer: &mut SdlRenderer,
+    event_pump: &mut EventPump,
+    game: &Game,
+) -> Result<Option<bool>, String> {
+    let mut view = View::new(CONTINUE_CONTROLS.iter().map(|line| line.to_string()).collect());
+    let frame = view.frame(game.tetris(), game.frame(), Mode::Continue);
+    renderer.render(&frame)?;
+    loop {
+        match event_pump.wait_event() {
+            Event::Quit { .. } => return Ok(None),
+            Event::KeyDown {
+                keycode: Some(Keycode::Y),
+                ..
+            } => return Ok(Some(true)),
+            Event::KeyDown {
+                keycode: Some(Keycode::N | Keycode::Escape),
+                ..
+            } => return Ok(Some(false)),
+            // A janela pode ter sido coberta ou redimensionada
+            Event::Window { .. } => renderer.render(&frame)?,
+            _ => {}
+        }
+    }
+}
+
+fn play(
+    renderer: &mut SdlRenderer,
+    event_pump: &mut EventPump,
+    mut game: Game,
+    mut recorder: Option<Recorder>,
+) -> Result<(), String> {
+    let mut view = View::new(PLAY_CONTROLS.iter().map(|line| line.to_string()).collect());
+    let mut inputs: Vec<Input> = Vec::new();
+    let mut handler = InputHandler::default();
+    let mut last_frame = Instant::now();
 
     'running: loop {
         // Event handling
@@ -88,96 +405,185 @@ fn main() -> Result<(), String> {
                     keycode: Some(Keycode::Escape),
                     ..
                 } => break 'running,
+                Event::KeyDown {
+                    keycode: Some(Keycode::Q),
+                    ..
+                } => {
+                    // Uma partida encerrada não tem o que continuar
+                    // Uma falha ao salvar não impede a gravação do replay
+                    if !game.tetris().is_game_over() {
+                        if let Err(e) = fs::write(SAVE_FILE, game.snapshot().to_string()) {
+                            eprintln!("Could not save the game: {}", e);
+                        }
+                    }
+                    break 'running;
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::G),
+                    ..
+                } => view.toggle_ghost(),
                 Event::KeyDown {
                     keycode: Some(keycode),
+                    repeat,
                     ..
-                } => match keycode {
-                    Keycode::Left => tetris.shift(Direction::Left),
-                    Keycode::Right => tetris.shift(Direction::Right),
-                    Keycode::Down => tetris.tick(),
-                    Keycode::Up | Keycode::W => tetris.rotate(),
-                    Keycode::Space => tetris.drop(),
-                    Keycode::R => tetris.reset(),
-                    _ => {}
-                },
+                } => {
+                    // A repetição do SO é ignorada; o handler cuida do DAS
+                    if let Some(button) = button_for_key(keycode) {
+                        handler.press(button);
+                    } else if let Some(input) = input_for_key(keycode).filter(|_| !repeat) {
+                        inputs.push(input);
+                    }
+                }
+                Event::KeyUp {
+                    keycode: Some(keycode),
+                    ..
+                } => {
+                    if let Some(button) = button_for_key(keycode) {
+                        handler.release(button);
+                    }
+                }
                 _ => {}
             }
         }
 
-        // Auto-tick
-        frame_count += 1;
-        if frame_count >= frames_per_tick {
-            tetris.tick();
-            frame_count = 0;
+        let now = Instant::now();
+        // Teclas apertadas desde o último frame entram no próximo
+        let frames = game.advance(now - last_frame, |tetris| {
+            let mut frame_inputs = std::mem::take(&mut inputs);
+            frame_inputs.extend(handler.frame_inputs(tetris));
+            frame_inputs
+        });
+        last_frame = now;
+        if let Some(recorder) = &mut recorder {
+            frames.iter().for_each(|inputs| recorder.record_frame(inputs));
         }
 
-        // Clear canvas
-        canvas.set_draw_color(Color::RGB(17, 17, 30));
-        canvas.clear();
+        let frame = game.frame();
+        view.update(game.tetris_mut().drain_events(), frame);
+        renderer.render(&view.frame(game.tetris(), frame, Mode::Playing))?;
 
-        let grid_offset_x = GRID_PADDING;
-        let grid_offset_y = GRID_PADDING;
+        ::std::thread::sleep(Duration::from_millis(1));
+    }
 
-        // Draw grid background
-        canvas.set_draw_color(Color::RGB(26, 26, 46));
-        canvas.fill_rect(Rect::new(
-            grid_offset_x - 5,
-            grid_offset_y - 5,
-            (grid_width * CELL_SIZE) as u32 + 10,
-            (grid_height * CELL_SIZE) as u32 + 10,
-        ))?;
+    if let Some(recorder) = recorder {
+        fs::write(REPLAY_FILE, recorder.replay().to_bytes()).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
 
-        // Draw cells
-        for pos in tetris.iter_position() {
-            let cell_type = tetris.get(pos).unwrap_or("empty");
-            draw_cell(&mut canvas, pos, cell_type, grid_offset_x, grid_offset_y);
+fn watch(renderer: &mut SdlRenderer, event_pump: &mut EventPump, replay: Replay) -> Result<(), String> {
+    let mut player = Player::new(replay);
+    let mut view = View::new(REPLAY_CONTROLS.iter().map(|line| line.to_string()).collect());
+    let mut last_frame = Instant::now();
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                Event::KeyDown {
+                    keycode: Some(keycode),
+                    ..
+                } => match keycode {
+                    Keycode::Space => player.toggle_pause(),
+                    Keycode::Up => player.set_speed(player.speed() * 2.0),
+                    Keycode::Down => player.set_speed(player.speed() / 2.0),
+                    Keycode::Left => player.seek(player.frame().saturating_sub(SEEK_FRAMES)),
+                    Keycode::Right => player.seek(player.frame() + SEEK_FRAMES),
+                    Keycode::G => view.toggle_ghost(),
+                    _ => {}
+                },
+                _ => {}
+            }
         }
 
-        // Draw info panel
-        let info_x = (grid_width * CELL_SIZE) as i32 + GRID_PADDING + 30;
-        let info_y = GRID_PADDING;
-
-        // Title
-        canvas.set_draw_color(Color::RGB(255, 255, 255));
-        draw_text(&mut canvas, "TETRIS", info_x, info_y, 32);
-
-        // Score
-        let score_text = format!("Score: {}", tetris.score());
-        canvas.set_draw_color(Color::RGB(100, 200, 255));
-        canvas.fill_rect(Rect::new(info_x, info_y + 60, 200, 60))?;
-        canvas.set_draw_color(Color::RGB(255, 255, 255));
-        draw_text(&mut canvas, &score_text, info_x + 10, info_y + 80, 24);
-
-        // Status
-        let status = if tetris.is_game_over() {
-            "GAME OVER!"
-        } else {
-            "Playing..."
+        let now = Instant::now();
+        player.update(now - last_frame);
+        last_frame = now;
+        let frame = player.frame();
+        view.update(player.tetris_mut().drain_events(), frame);
+        let mode = Mode::Replay {
+            paused: player.is_paused(),
+            speed: player.speed(),
+            total_frames: player.total_frames(),
         };
-        let status_color = if tetris.is_game_over() {
-            Color::RGB(239, 68, 68)
-        } else {
-            Color::RGB(16, 185, 129)
-        };
-        canvas.set_draw_color(status_color);
-        canvas.fill_rect(Rect::new(info_x, info_y + 140, 200, 40))?;
-        canvas.set_draw_color(Color::RGB(255, 255, 255));
-        draw_text(&mut canvas, status, info_x + 10, info_y + 150, 16);
-
-        // Controls info
-        let controls_y = info_y + 220;
-        canvas.set_draw_color(Color::RGB(200, 200, 200));
-        draw_text(&mut canvas, "Controls:", info_x, controls_y, 18);
-        draw_text(&mut canvas, "< > : Move", info_x, controls_y + 30, 14);
-        draw_text(&mut canvas, "^ / W : Rotate", info_x, controls_y + 50, 14);
-        draw_text(&mut canvas, "v : Soft Drop", info_x, controls_y + 70, 14);
-        draw_text(&mut canvas, "Space : Hard Drop", info_x, controls_y + 90, 14);
-        draw_text(&mut canvas, "R : Restart", info_x, controls_y + 110, 14);
-        draw_text(&mut canvas, "ESC : Quit", info_x, controls_y + 130, 14);
-
-        canvas.present();
-        ::std::thread::sleep(Duration::from_millis(16)); // ~60 FPS
+        renderer.render(&view.frame(player.tetris(), frame, mode))?;
+
+        ::std::thread::sleep(Duration::from_millis(16));
     }
 
     Ok(())
 }
+
+fn main() -> Result<(), String> {
+    // `tetris --replay arquivo` assiste a uma partida gravada
+    let args: Vec<String> = std::env::args().collect();
+    let replay = match args.iter().position(|arg| arg == "--replay") {
+        Some(i) => {
+            let path = args.get(i + 1).map_or(REPLAY_FILE, String::as_str);
+            let bytes = fs::read(path).map_err(|e| format!("{}: {}", path, e))?;
+            Some(Replay::from_bytes(&bytes).map_err(|e| e.to_string())?)
+        }
+        None => None,
+    };
+
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let saved = match replay {
+        Some(_) => None,
+        None => Game::load_saved(SAVE_FILE).unwrap_or_else(|e| {
+            eprintln!("Could not load the saved game: {}", e);
+            None
+        }),
+    };
+    let (grid_width, grid_height) = match (&replay, &saved) {
+        (Some(replay), _) => (replay.width, replay.height),
+        (None, Some(game)) => (game.tetris().width() as u32, game.tetris().height() as u32),
+        (None, None) => (10, 20),
+    };
+    let info_panel_width = 330;
+    
+    let window_width = grid_width * CELL_SIZE + 2 * GRID_PADDING as u32 + info_panel_width;
+    let window_height = grid_height * CELL_SIZE + 2 * GRID_PADDING as u32;
+
+    let window = video_subsystem
+        .window("🎮 Tetris Game", window_width, window_height)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let canvas = window
+        .into_canvas()
+        .accelerated()
+        .present_vsync()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let texture_creator = canvas.texture_creator();
+    let font = load_font(&texture_creator)?;
+    let mut renderer = SdlRenderer { canvas, font };
+    let mut event_pump = sdl_context.event_pump()?;
+    if let Some(replay) = replay {
+        return watch(&mut renderer, &mut event_pump, replay);
+    }
+
+    let saved = match saved {
+        Some(game) => match ask_continue(&mut renderer, &mut event_pump, &game)? {
+            Some(resume) => resume.then_some(game),
+            None => return Ok(()),
+        },
+        None => None,
+    };
+
+    match saved {
+        Some(game) => play(&mut renderer, &mut event_pump, game, None),
+        None => {
+            let (game, recorder) = Recorder::new_game(grid_width, grid_height);
+            play(&mut renderer, &mut event_pump, game, Some(recorder))
+        }
+    }
+}